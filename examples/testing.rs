@@ -1,4 +1,3 @@
-// use passcod_networkmanager::devices::{Any, Device, Wired, Wireless};
 use passcod_networkmanager::{Error, NetworkManager};
 
 #[tokio::main]
@@ -13,31 +12,35 @@ async fn main() -> Result<(), Error> {
                 let raw = ap.ssid().await?;
                 println!("SSID: {} {raw:02x?}", String::from_utf8_lossy(&raw));
             }
-            // } else if let Some(eth) = dev.to_ethernet().await? {
-            //     println!("Is autoconnected: {:?}", x.autoconnect()?);
-            //     println!("Speed: {:?}", x.speed()?);
-            //     println!("S390 Subchannels: {:?}", x.s390_subchannels()?);
-            //     println!("Carrier: {:?}", x.carrier()?);
-            //     let conf = x.ip4_config()?;
-            //     println!("Gateway: {:?}", conf.gateway()?);
-            //     let con = x.active_connection()?;
-            //     println!("Connection id: {}", con.id()?);
+        } else if let Some(eth) = dev.to_wired().await? {
+            println!("Is autoconnected: {:?}", dev.can_autoconnect().await?);
+            println!("Speed: {:?}", eth.speed().await?);
+            println!("S390 Subchannels: {:?}", eth.s390_subchannels().await?);
+            println!("Carrier: {:?}", eth.carrier().await?);
+            if let Some(conf) = dev.ip4_config().await? {
+                println!("Gateway: {:?}", conf.gateway().await?);
+            }
+            if let Some(con) = dev.active_connection().await? {
+                println!("Connection id: {}", con.id().await?);
+            }
         }
     }
 
-    // let eth0 = nm.get_device_by_ip_interface_name("eth0").await?;
-    // match eth0 {
-    //     Device::Ethernet(x) => {
-    //         // NetworkManager >= 1.24
-    //         // println!("Hardware Address: {:?}", Any::hw_address(&x)?);
-
-    //         // NetworkManager < 1.24
-    //         // println!("Hardware Address: {:?}", Wired::hw_address(&x)?);
-
-    //         println!("Speed: {:?}", x.speed()?);
-    //     }
-    //     _ => {}
-    // }
+    // NetworkManager fails the lookup when there is no such interface
+    match nm.get_device_by_ip_interface_name("eth0").await {
+        Ok(eth0) => {
+            if let Some(eth) = eth0.to_wired().await? {
+                println!("Hardware Address: {:?}", eth0.hardware_address().await?);
+                println!("Speed: {:?}", eth.speed().await?);
+            }
+        }
+        Err(Error::ZBus(zbus::Error::MethodError(name, _, _)))
+            if name.as_str() == "org.freedesktop.NetworkManager.UnknownDevice" =>
+        {
+            println!("No eth0");
+        }
+        Err(err) => return Err(err),
+    }
 
     Ok(())
 }
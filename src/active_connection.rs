@@ -0,0 +1,252 @@
+use std::time::Duration;
+
+use futures_lite::{Stream, StreamExt};
+use num_traits::FromPrimitive;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::{
+    connection::Connection,
+    device::Device,
+    raw::connection_active::ConnectionActiveProxy,
+    types::{ActivationStateFlags, ActiveConnectionState, ActiveConnectionStateReason},
+    util::optional_path,
+    Dhcp4Config, Dhcp6Config, Error, Ip4Config, Ip6Config,
+};
+
+/// An active connection.
+///
+/// This is a [`Connection`] profile that has been activated, usually on a device. It is obtained
+/// when activating a connection with
+/// [`NetworkManager::activate_connection()`](crate::NetworkManager::activate_connection()), and
+/// lives until the connection is deactivated.
+#[derive(Clone, Debug)]
+pub struct ActiveConnection {
+    pub(crate) zbus: zbus::Connection,
+    pub(crate) path: OwnedObjectPath,
+}
+
+crate::zproxy_pathed!(ActiveConnection, ConnectionActiveProxy<'_>);
+
+impl ActiveConnection {
+    /// Subscribe to the state changes of this active connection.
+    ///
+    /// Changes that occurred before this method was called are not reported.
+    pub async fn state_changes(
+        &self,
+    ) -> Result<impl Stream<Item = Result<ActiveConnectionStateChange, Error>> + Unpin, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .receive_state_changed()
+            .await?
+            .map(|signal| {
+                let args = signal.args()?;
+                Ok(ActiveConnectionStateChange {
+                    state: FromPrimitive::from_u32(args.state).ok_or(Error::UnsupportedType)?,
+                    reason: FromPrimitive::from_u32(args.reason).ok_or(Error::UnsupportedType)?,
+                })
+            }))
+    }
+
+    /// Wait until this connection is fully activated.
+    ///
+    /// Resolves to the active connection once it reaches [`ActiveConnectionState::Activated`]. If
+    /// the connection is deactivated instead, this fails with [`Error::ActivationFailed`] carrying
+    /// the reason given by NetworkManager, and if neither happens within `timeout`, it fails with
+    /// [`Error::Timeout`].
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use passcod_networkmanager::{connection::Connection, device::Device, Error, NetworkManager};
+    /// # async fn example(nm: NetworkManager, profile: Connection, device: Device) -> Result<(), Error> {
+    /// let active = nm
+    ///     .activate_connection(&profile, Some(&device), None)
+    ///     .await?
+    ///     .wait_activated(Duration::from_secs(30))
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn wait_activated(self, timeout: Duration) -> Result<Self, Error> {
+        // subscribe before reading the current state so no change is missed in between
+        let mut changes = self.state_changes().await?;
+        crate::util::timeout(timeout, async {
+            match self.state().await? {
+                ActiveConnectionState::Activated => return Ok(()),
+                ActiveConnectionState::Deactivated => {
+                    return Err(Error::ActivationFailed(
+                        ActiveConnectionStateReason::Unknown,
+                    ))
+                }
+                _ => {}
+            }
+
+            while let Some(change) = changes.next().await {
+                let change = change?;
+                match change.state {
+                    ActiveConnectionState::Activated => return Ok(()),
+                    ActiveConnectionState::Deactivating | ActiveConnectionState::Deactivated => {
+                        return Err(Error::ActivationFailed(change.reason))
+                    }
+                    _ => {}
+                }
+            }
+
+            // the stream only ends if the bus connection goes away; let the timeout fire
+            std::future::pending().await
+        })
+        .await?;
+        Ok(self)
+    }
+}
+
+impl ActiveConnection {
+    /// The connection profile this active connection was activated from.
+    pub async fn connection(&self) -> Result<Connection, Error> {
+        let path = self.raw().await?.connection().await?;
+        Ok(Connection {
+            zbus: self.zbus.clone(),
+            path,
+        })
+    }
+
+    /// The ID of the connection, provided as a convenience so that clients do not have to retrieve
+    /// all connection details.
+    pub async fn id(&self) -> Result<String, Error> {
+        self.raw().await?.id().await.map_err(Error::ZBus)
+    }
+
+    /// The UUID of the connection, provided as a convenience so that clients do not have to
+    /// retrieve all connection details.
+    pub async fn uuid(&self) -> Result<String, Error> {
+        self.raw().await?.uuid().await.map_err(Error::ZBus)
+    }
+
+    /// The type of the connection, provided as a convenience so that clients do not have to
+    /// retrieve all connection details.
+    ///
+    /// This is the setting name of the connection's base type, eg `802-3-ethernet`.
+    pub async fn connection_type(&self) -> Result<String, Error> {
+        self.raw().await?.type_().await.map_err(Error::ZBus)
+    }
+
+    /// The devices this connection is active on.
+    pub async fn devices(&self) -> Result<impl Iterator<Item = Device> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .devices()
+            .await?
+            .into_iter()
+            .map(|path| Device {
+                zbus: self.zbus.clone(),
+                path,
+            }))
+    }
+
+    /// The path of the connection-type specific object this connection was activated with.
+    ///
+    /// For Wi-Fi connections this is the access point, and for VPN connections the parent active
+    /// connection. A value of None means there is no specific object.
+    pub async fn specific_object(&self) -> Result<Option<OwnedObjectPath>, Error> {
+        let path = self.raw().await?.specific_object().await?;
+        Ok(optional_path(path))
+    }
+
+    /// The state of this active connection.
+    pub async fn state(&self) -> Result<ActiveConnectionState, Error> {
+        let state = self.raw().await?.state_property().await?;
+        FromPrimitive::from_u32(state).ok_or(Error::UnsupportedType)
+    }
+
+    /// Flags describing the current activation state.
+    pub async fn state_flags(&self) -> Result<ActivationStateFlags, Error> {
+        let value = self.raw().await?.state_flags().await?;
+        Ok(ActivationStateFlags::from_bits_retain(value))
+    }
+
+    /// Whether this active connection is the default IPv4 connection.
+    ///
+    /// This means it currently owns the default IPv4 route.
+    pub async fn is_default(&self) -> Result<bool, Error> {
+        self.raw().await?.default().await.map_err(Error::ZBus)
+    }
+
+    /// Whether this active connection is the default IPv6 connection.
+    ///
+    /// This means it currently owns the default IPv6 route.
+    pub async fn is_default6(&self) -> Result<bool, Error> {
+        self.raw().await?.default6().await.map_err(Error::ZBus)
+    }
+
+    /// The IPv4 configuration of the connection.
+    ///
+    /// This is only valid when the connection is in the [`ActiveConnectionState::Activated`] state.
+    pub async fn ip4_config(&self) -> Result<Option<Ip4Config>, Error> {
+        let path = self.raw().await?.ip4_config().await?;
+        Ok(optional_path(path).map(|path| Ip4Config {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The DHCPv4 configuration of the connection.
+    ///
+    /// This is only valid when the connection is in the [`ActiveConnectionState::Activated`] state,
+    /// and is None if the connection doesn't use DHCPv4.
+    pub async fn dhcp4_config(&self) -> Result<Option<Dhcp4Config>, Error> {
+        let path = self.raw().await?.dhcp4_config().await?;
+        Ok(optional_path(path).map(|path| Dhcp4Config {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The IPv6 configuration of the connection.
+    ///
+    /// This is only valid when the connection is in the [`ActiveConnectionState::Activated`] state.
+    pub async fn ip6_config(&self) -> Result<Option<Ip6Config>, Error> {
+        let path = self.raw().await?.ip6_config().await?;
+        Ok(optional_path(path).map(|path| Ip6Config {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The DHCPv6 configuration of the connection.
+    ///
+    /// This is only valid when the connection is in the [`ActiveConnectionState::Activated`] state,
+    /// and is None if the connection doesn't use DHCPv6.
+    pub async fn dhcp6_config(&self) -> Result<Option<Dhcp6Config>, Error> {
+        let path = self.raw().await?.dhcp6_config().await?;
+        Ok(optional_path(path).map(|path| Dhcp6Config {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// Whether this active connection is a VPN connection.
+    pub async fn is_vpn(&self) -> Result<bool, Error> {
+        self.raw().await?.vpn().await.map_err(Error::ZBus)
+    }
+
+    /// The controller device, if this connection is a port of a bond, bridge, team, etc.
+    pub async fn controller(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.controller().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
+}
+
+/// A state change of an active connection.
+///
+/// Obtained from [`ActiveConnection::state_changes()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ActiveConnectionStateChange {
+    /// The new state of the active connection.
+    pub state: ActiveConnectionState,
+
+    /// The reason for the change.
+    pub reason: ActiveConnectionStateReason,
+}
@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use num_traits::FromPrimitive;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::{
+    device::Device, raw::checkpoint::CheckpointProxy, types::RollbackResult, Error, NetworkManager,
+};
+
+/// A snapshot of the network configuration.
+///
+/// Checkpoints are created with [`NetworkManager::checkpoint_create()`]. They can be rolled back
+/// to restore the configuration of the devices they cover, or destroyed to keep the current
+/// configuration. If a checkpoint has a rollback timeout, NetworkManager automatically rolls it
+/// back when the timeout expires, unless it was destroyed before then.
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    pub(crate) zbus: zbus::Connection,
+    pub(crate) path: OwnedObjectPath,
+}
+
+crate::zproxy_pathed!(Checkpoint, CheckpointProxy<'_>);
+
+impl Checkpoint {
+    fn manager(&self) -> NetworkManager {
+        NetworkManager::new_with_zbus(self.zbus.clone())
+    }
+
+    /// Roll back the configuration to this checkpoint.
+    ///
+    /// This destroys the checkpoint. Returns the result of the rollback for each device covered by
+    /// the checkpoint.
+    pub async fn rollback(&self) -> Result<Vec<(Device, RollbackResult)>, Error> {
+        self.manager()
+            .raw()
+            .await?
+            .checkpoint_rollback(&self.path)
+            .await?
+            .into_iter()
+            .map(|(path, result)| {
+                Ok((
+                    Device {
+                        zbus: self.zbus.clone(),
+                        path: OwnedObjectPath::try_from(path).map_err(zbus::Error::from)?,
+                    },
+                    FromPrimitive::from_u32(result).ok_or(Error::UnsupportedType)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Destroy this checkpoint without performing a rollback.
+    ///
+    /// This keeps the current configuration.
+    pub async fn destroy(&self) -> Result<(), Error> {
+        self.manager()
+            .raw()
+            .await?
+            .checkpoint_destroy(&self.path)
+            .await
+            .map_err(Error::ZBus)
+    }
+
+    /// Reset the rollback timeout of this checkpoint.
+    ///
+    /// The new timeout is `add_timeout` seconds from now, not from when the checkpoint was created.
+    /// Passing `0` disables the automatic rollback.
+    pub async fn adjust_rollback_timeout(&self, add_timeout: u32) -> Result<(), Error> {
+        self.manager()
+            .raw()
+            .await?
+            .checkpoint_adjust_rollback_timeout(&self.path, add_timeout)
+            .await
+            .map_err(Error::ZBus)
+    }
+
+    /// Wrap this checkpoint in a guard that rolls it back unless committed.
+    ///
+    /// This resets the rollback timeout of the checkpoint to `rollback_timeout` from now, rounded
+    /// up to whole seconds, so that it is rolled back even if the guard is dropped. Fails if the
+    /// timeout is zero, as that would disable the automatic rollback.
+    ///
+    /// See [`CheckpointGuard`].
+    pub async fn into_guard(self, rollback_timeout: Duration) -> Result<CheckpointGuard, Error> {
+        self.adjust_rollback_timeout(timeout_secs(rollback_timeout)?)
+            .await?;
+        Ok(CheckpointGuard { checkpoint: self })
+    }
+}
+
+impl Checkpoint {
+    /// The timestamp of when the checkpoint was created.
+    ///
+    /// This is in `CLOCK_BOOTTIME` milliseconds.
+    pub async fn created(&self) -> Result<i64, Error> {
+        self.raw().await?.created().await.map_err(Error::ZBus)
+    }
+
+    /// The devices that are part of this checkpoint.
+    pub async fn devices(&self) -> Result<impl Iterator<Item = Device> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .devices()
+            .await?
+            .into_iter()
+            .map(|path| Device {
+                zbus: self.zbus.clone(),
+                path,
+            }))
+    }
+
+    /// The timeout in seconds for automatic rollback.
+    ///
+    /// A value of None means the checkpoint is never rolled back automatically.
+    pub async fn rollback_timeout(&self) -> Result<Option<u32>, Error> {
+        let timeout = self.raw().await?.rollback_timeout().await?;
+        Ok(if timeout == 0 { None } else { Some(timeout) })
+    }
+}
+
+/// The whole seconds of a rollback timeout, rounded up, which must not be zero.
+fn timeout_secs(timeout: Duration) -> Result<u32, Error> {
+    if timeout.is_zero() {
+        return Err(Error::InvalidSetting(
+            "a checkpoint guard needs a rollback timeout".into(),
+        ));
+    }
+
+    let secs = timeout
+        .as_secs()
+        .saturating_add(u64::from(timeout.subsec_nanos() > 0));
+    Ok(u32::try_from(secs).unwrap_or(u32::MAX))
+}
+
+/// A checkpoint that is rolled back unless explicitly committed.
+///
+/// This is obtained from [`Checkpoint::into_guard()`]. Make the configuration changes you want to
+/// make, verify that they work (for example, that you can still reach the machine), and then call
+/// [`CheckpointGuard::commit()`] to keep them, or [`CheckpointGuard::rollback()`] to undo them.
+///
+/// Dropping the guard does neither: the checkpoint is left to NetworkManager, which rolls it back
+/// when the rollback timeout given to [`Checkpoint::into_guard()`] expires. This also covers the
+/// process exiting or losing its connection to the bus.
+#[derive(Debug)]
+#[must_use = "dropping the guard leaves the checkpoint to its rollback timeout"]
+pub struct CheckpointGuard {
+    checkpoint: Checkpoint,
+}
+
+impl CheckpointGuard {
+    /// Get the underlying [`Checkpoint`].
+    pub fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    /// Keep the current configuration, destroying the checkpoint.
+    pub async fn commit(self) -> Result<(), Error> {
+        self.checkpoint.destroy().await
+    }
+
+    /// Roll back the configuration to the checkpoint now.
+    ///
+    /// See [`Checkpoint::rollback()`].
+    pub async fn rollback(self) -> Result<Vec<(Device, RollbackResult)>, Error> {
+        self.checkpoint.rollback().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_timeout() {
+        assert!(timeout_secs(Duration::ZERO).is_err());
+        assert_eq!(timeout_secs(Duration::from_millis(1)).unwrap(), 1);
+        assert_eq!(timeout_secs(Duration::from_secs(30)).unwrap(), 30);
+        assert_eq!(timeout_secs(Duration::from_millis(30_500)).unwrap(), 31);
+        assert_eq!(timeout_secs(Duration::MAX).unwrap(), u32::MAX);
+    }
+}
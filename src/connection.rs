@@ -1,8 +1,12 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::path::PathBuf;
 
-use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+use zbus::zvariant::OwnedObjectPath;
 
-use crate::{types::ConnectionFlags, Error};
+use crate::{
+    setting::{borrow_dbus, ConnectionSettings, Setting, WireGuardSetting},
+    types::ConnectionFlags,
+    Error,
+};
 
 /// A connection profile.
 ///
@@ -26,13 +30,14 @@ impl Connection {
     ///
     /// Secrets may be part of the update request, and will be either stored in persistent storage
     /// or sent to a Secret Agent for storage, depending on the flags associated with each secret.
-    pub async fn update(
-        &self,
-        properties: HashMap<&str, HashMap<&str, Value<'_>>>,
-    ) -> Result<(), Error> {
+    ///
+    /// The settings are checked with [`ConnectionSettings::validate()`] first.
+    pub async fn update(&self, settings: &ConnectionSettings) -> Result<(), Error> {
+        settings.validate()?;
+        let raw = settings.to_dbus()?;
         self.raw()
             .await?
-            .update(properties)
+            .update(borrow_dbus(&raw)?)
             .await
             .map_err(Error::ZBus)
     }
@@ -48,13 +53,14 @@ impl Connection {
     ///
     /// Note that unsaved changes will be lost if the connection is reloaded from disk (either
     /// automatically on file change or due to an explicit `ReloadConnections` call).
-    pub async fn update_in_memory(
-        &self,
-        properties: HashMap<&str, HashMap<&str, Value<'_>>>,
-    ) -> Result<(), Error> {
+    ///
+    /// The settings are checked with [`ConnectionSettings::validate()`] first.
+    pub async fn update_in_memory(&self, settings: &ConnectionSettings) -> Result<(), Error> {
+        settings.validate()?;
+        let raw = settings.to_dbus()?;
         self.raw()
             .await?
-            .update_unsaved(properties)
+            .update_unsaved(borrow_dbus(&raw)?)
             .await
             .map_err(Error::ZBus)
     }
@@ -68,15 +74,17 @@ impl Connection {
     ///
     /// This will never include any secrets required for connection to the network, as those are
     /// often protected. Secrets must be requested separately using the `GetSecrets` call.
-    pub async fn settings(&self) -> Result<HashMap<String, HashMap<String, OwnedValue>>, Error> {
-        self.raw().await?.get_settings().await.map_err(Error::ZBus)
+    pub async fn settings(&self) -> Result<ConnectionSettings, Error> {
+        ConnectionSettings::from_dbus(self.raw().await?.get_settings().await?)
     }
 
     /// Get the secrets belonging to this network configuration.
     ///
     /// Only secrets from persistent storage or a Secret Agent running in the requestor's session
     /// will be returned. The user will never be prompted for secrets as a result of this request.
-    pub async fn secrets(&self) -> Result<HashMap<String, HashMap<String, OwnedValue>>, Error> {
+    ///
+    /// The secrets are returned as partial settings, containing only the secret properties.
+    pub async fn secrets(&self) -> Result<ConnectionSettings, Error> {
         self.secrets_for_setting("").await
     }
 
@@ -87,12 +95,23 @@ impl Connection {
     pub async fn secrets_for_setting(
         &self,
         setting_name: &str,
-    ) -> Result<HashMap<String, HashMap<String, OwnedValue>>, Error> {
-        self.raw()
-            .await?
-            .get_secrets(setting_name)
-            .await
-            .map_err(Error::ZBus)
+    ) -> Result<ConnectionSettings, Error> {
+        ConnectionSettings::from_dbus(self.raw().await?.get_secrets(setting_name).await?)
+    }
+
+    /// Export this WireGuard connection as a wg-quick configuration file.
+    ///
+    /// This fetches the settings and the secrets of the connection, and fails if the secrets are
+    /// not available to the caller. See [`ConnectionSettings::to_wg_quick()`].
+    pub async fn to_wg_quick(&self) -> Result<String, Error> {
+        let mut settings = self.settings().await?;
+        if let Some(wireguard) = &mut settings.wireguard {
+            let secrets = self.secrets_for_setting(WireGuardSetting::NAME).await?;
+            if let Some(secrets) = secrets.wireguard {
+                wireguard.merge_secrets(secrets);
+            }
+        }
+        settings.to_wg_quick()
     }
 
     /// Clear the secrets belonging to this network connection profile.
@@ -4,10 +4,39 @@ use zbus::{zvariant::OwnedObjectPath, Connection};
 use crate::{raw::device::DeviceProxy, types::DeviceType, Error};
 
 #[doc(inline)]
-pub use _methods::AppliedConnection;
+pub use _methods::{AppliedConnection, DeviceStateTransition};
 
 mod _methods;
+pub mod adsl;
+pub mod bluetooth;
+pub mod bond;
+pub mod bridge;
+pub mod dummy;
+pub mod generic;
+pub mod infiniband;
+pub mod ip_tunnel;
+pub mod loopback;
+pub mod lowpan;
+pub mod macsec;
+pub mod macvlan;
+pub mod modem;
+pub mod olpc_mesh;
+pub mod ovs_bridge;
+pub mod ovs_interface;
+pub mod ovs_port;
+pub mod ppp;
+pub mod team;
+pub mod tun;
+pub mod veth;
+pub mod vlan;
+pub mod vrf;
+pub mod vxlan;
+pub mod wifi_p2p;
+pub mod wimax;
+pub mod wired;
+pub mod wireguard;
 pub mod wireless;
+pub mod wpan;
 
 /// A network device.
 ///
@@ -28,6 +57,160 @@ impl Device {
         let dev_type = self.raw().await?.device_type().await?;
         FromPrimitive::from_u32(dev_type).ok_or(Error::UnsupportedType)
     }
+
+    /// Cast this device to the facade for its type.
+    ///
+    /// This is the same as calling the `to_*` method matching the
+    /// [device type](Device::device_type()), but with a single D-Bus call.
+    pub async fn specific(&self) -> Result<SpecificDevice, Error> {
+        let device = self.clone();
+        Ok(match self.device_type().await? {
+            DeviceType::Adsl => SpecificDevice::Adsl(adsl::AdslDevice::new(device)),
+            DeviceType::Bt => SpecificDevice::Bluetooth(bluetooth::BluetoothDevice::new(device)),
+            DeviceType::Bond => SpecificDevice::Bond(bond::BondDevice::new(device)),
+            DeviceType::Bridge => SpecificDevice::Bridge(bridge::BridgeDevice::new(device)),
+            DeviceType::Dummy => SpecificDevice::Dummy(dummy::DummyDevice::new(device)),
+            DeviceType::Generic => SpecificDevice::Generic(generic::GenericDevice::new(device)),
+            DeviceType::Infiniband => {
+                SpecificDevice::Infiniband(infiniband::InfinibandDevice::new(device))
+            }
+            DeviceType::IpTunnel => {
+                SpecificDevice::IpTunnel(ip_tunnel::IpTunnelDevice::new(device))
+            }
+            DeviceType::Loopback => SpecificDevice::Loopback(loopback::LoopbackDevice::new(device)),
+            DeviceType::SixLowpan => SpecificDevice::Lowpan(lowpan::LowpanDevice::new(device)),
+            DeviceType::Macsec => SpecificDevice::Macsec(macsec::MacsecDevice::new(device)),
+            DeviceType::Macvlan => SpecificDevice::Macvlan(macvlan::MacvlanDevice::new(device)),
+            DeviceType::Modem => SpecificDevice::Modem(modem::ModemDevice::new(device)),
+            DeviceType::OlpcMesh => {
+                SpecificDevice::OlpcMesh(olpc_mesh::OlpcMeshDevice::new(device))
+            }
+            DeviceType::OvsBridge => {
+                SpecificDevice::OvsBridge(ovs_bridge::OvsBridgeDevice::new(device))
+            }
+            DeviceType::OvsInterface => {
+                SpecificDevice::OvsInterface(ovs_interface::OvsInterfaceDevice::new(device))
+            }
+            DeviceType::OvsPort => SpecificDevice::OvsPort(ovs_port::OvsPortDevice::new(device)),
+            DeviceType::Ppp => SpecificDevice::Ppp(ppp::PppDevice::new(device)),
+            DeviceType::Team => SpecificDevice::Team(team::TeamDevice::new(device)),
+            DeviceType::Tun => SpecificDevice::Tun(tun::TunDevice::new(device)),
+            DeviceType::Veth => SpecificDevice::Veth(veth::VethDevice::new(device)),
+            DeviceType::Vlan => SpecificDevice::Vlan(vlan::VlanDevice::new(device)),
+            DeviceType::Vrf => SpecificDevice::Vrf(vrf::VrfDevice::new(device)),
+            DeviceType::Vxlan => SpecificDevice::Vxlan(vxlan::VxlanDevice::new(device)),
+            DeviceType::WifiP2p => SpecificDevice::WifiP2p(wifi_p2p::WifiP2pDevice::new(device)),
+            DeviceType::Wimax => SpecificDevice::Wimax(wimax::WimaxDevice::new(device)),
+            DeviceType::Ethernet => SpecificDevice::Wired(wired::WiredDevice::new(device)),
+            DeviceType::Wireguard => {
+                SpecificDevice::WireGuard(wireguard::WireGuardDevice::new(device))
+            }
+            DeviceType::Wifi => SpecificDevice::Wireless(wireless::WirelessDevice::new(device)),
+            DeviceType::Wpan => SpecificDevice::Wpan(wpan::WpanDevice::new(device)),
+            DeviceType::Unknown | DeviceType::Unused1 | DeviceType::Unused2 => {
+                SpecificDevice::Unknown(device)
+            }
+        })
+    }
+}
+
+/// A network device, cast to the facade for its type.
+///
+/// Obtained from [`Device::specific()`].
+#[derive(Clone, Debug)]
+pub enum SpecificDevice {
+    /// See [`AdslDevice`](adsl::AdslDevice).
+    Adsl(adsl::AdslDevice),
+
+    /// See [`BluetoothDevice`](bluetooth::BluetoothDevice).
+    Bluetooth(bluetooth::BluetoothDevice),
+
+    /// See [`BondDevice`](bond::BondDevice).
+    Bond(bond::BondDevice),
+
+    /// See [`BridgeDevice`](bridge::BridgeDevice).
+    Bridge(bridge::BridgeDevice),
+
+    /// See [`DummyDevice`](dummy::DummyDevice).
+    Dummy(dummy::DummyDevice),
+
+    /// See [`GenericDevice`](generic::GenericDevice).
+    Generic(generic::GenericDevice),
+
+    /// See [`InfinibandDevice`](infiniband::InfinibandDevice).
+    Infiniband(infiniband::InfinibandDevice),
+
+    /// See [`IpTunnelDevice`](ip_tunnel::IpTunnelDevice).
+    IpTunnel(ip_tunnel::IpTunnelDevice),
+
+    /// See [`LoopbackDevice`](loopback::LoopbackDevice).
+    Loopback(loopback::LoopbackDevice),
+
+    /// See [`LowpanDevice`](lowpan::LowpanDevice).
+    Lowpan(lowpan::LowpanDevice),
+
+    /// See [`MacsecDevice`](macsec::MacsecDevice).
+    Macsec(macsec::MacsecDevice),
+
+    /// See [`MacvlanDevice`](macvlan::MacvlanDevice).
+    Macvlan(macvlan::MacvlanDevice),
+
+    /// See [`ModemDevice`](modem::ModemDevice).
+    Modem(modem::ModemDevice),
+
+    /// See [`OlpcMeshDevice`](olpc_mesh::OlpcMeshDevice).
+    OlpcMesh(olpc_mesh::OlpcMeshDevice),
+
+    /// See [`OvsBridgeDevice`](ovs_bridge::OvsBridgeDevice).
+    OvsBridge(ovs_bridge::OvsBridgeDevice),
+
+    /// See [`OvsInterfaceDevice`](ovs_interface::OvsInterfaceDevice).
+    OvsInterface(ovs_interface::OvsInterfaceDevice),
+
+    /// See [`OvsPortDevice`](ovs_port::OvsPortDevice).
+    OvsPort(ovs_port::OvsPortDevice),
+
+    /// See [`PppDevice`](ppp::PppDevice).
+    Ppp(ppp::PppDevice),
+
+    /// See [`TeamDevice`](team::TeamDevice).
+    Team(team::TeamDevice),
+
+    /// See [`TunDevice`](tun::TunDevice).
+    Tun(tun::TunDevice),
+
+    /// See [`VethDevice`](veth::VethDevice).
+    Veth(veth::VethDevice),
+
+    /// See [`VlanDevice`](vlan::VlanDevice).
+    Vlan(vlan::VlanDevice),
+
+    /// See [`VrfDevice`](vrf::VrfDevice).
+    Vrf(vrf::VrfDevice),
+
+    /// See [`VxlanDevice`](vxlan::VxlanDevice).
+    Vxlan(vxlan::VxlanDevice),
+
+    /// See [`WifiP2pDevice`](wifi_p2p::WifiP2pDevice).
+    WifiP2p(wifi_p2p::WifiP2pDevice),
+
+    /// See [`WimaxDevice`](wimax::WimaxDevice).
+    Wimax(wimax::WimaxDevice),
+
+    /// See [`WiredDevice`](wired::WiredDevice).
+    Wired(wired::WiredDevice),
+
+    /// See [`WireGuardDevice`](wireguard::WireGuardDevice).
+    WireGuard(wireguard::WireGuardDevice),
+
+    /// See [`WirelessDevice`](wireless::WirelessDevice).
+    Wireless(wireless::WirelessDevice),
+
+    /// See [`WpanDevice`](wpan::WpanDevice).
+    Wpan(wpan::WpanDevice),
+
+    /// A device of an unknown type.
+    Unknown(Device),
 }
 
 macro_rules! cast {
@@ -45,4 +228,37 @@ macro_rules! cast {
     };
 }
 
+cast!(to_adsl, Adsl, adsl::AdslDevice);
+cast!(to_bluetooth, Bt, bluetooth::BluetoothDevice);
+cast!(to_bond, Bond, bond::BondDevice);
+cast!(to_bridge, Bridge, bridge::BridgeDevice);
+cast!(to_dummy, Dummy, dummy::DummyDevice);
+cast!(to_generic, Generic, generic::GenericDevice);
+cast!(to_infiniband, Infiniband, infiniband::InfinibandDevice);
+cast!(to_ip_tunnel, IpTunnel, ip_tunnel::IpTunnelDevice);
+cast!(to_loopback, Loopback, loopback::LoopbackDevice);
+cast!(to_lowpan, SixLowpan, lowpan::LowpanDevice);
+cast!(to_macsec, Macsec, macsec::MacsecDevice);
+cast!(to_macvlan, Macvlan, macvlan::MacvlanDevice);
+cast!(to_modem, Modem, modem::ModemDevice);
+cast!(to_olpc_mesh, OlpcMesh, olpc_mesh::OlpcMeshDevice);
+cast!(to_ovs_bridge, OvsBridge, ovs_bridge::OvsBridgeDevice);
+cast!(
+    to_ovs_interface,
+    OvsInterface,
+    ovs_interface::OvsInterfaceDevice
+);
+cast!(to_ovs_port, OvsPort, ovs_port::OvsPortDevice);
+cast!(to_ppp, Ppp, ppp::PppDevice);
+cast!(to_team, Team, team::TeamDevice);
+cast!(to_tun, Tun, tun::TunDevice);
+cast!(to_veth, Veth, veth::VethDevice);
+cast!(to_vlan, Vlan, vlan::VlanDevice);
+cast!(to_vrf, Vrf, vrf::VrfDevice);
+cast!(to_vxlan, Vxlan, vxlan::VxlanDevice);
+cast!(to_wifi_p2p, WifiP2p, wifi_p2p::WifiP2pDevice);
+cast!(to_wimax, Wimax, wimax::WimaxDevice);
+cast!(to_wired, Ethernet, wired::WiredDevice);
+cast!(to_wireguard, Wireguard, wireguard::WireGuardDevice);
 cast!(to_wireless, Wifi, wireless::WirelessDevice);
+cast!(to_wpan, Wpan, wpan::WpanDevice);
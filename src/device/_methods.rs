@@ -1,15 +1,18 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
+use futures_lite::{Stream, StreamExt};
 use num_traits::FromPrimitive;
-use zbus::zvariant::{OwnedValue, Value};
+use zbus::zvariant::OwnedValue;
 
 use crate::{
-    ip4config::Ip4Config,
+    active_connection::ActiveConnection,
+    setting::{borrow_dbus, ConnectionSettings},
     types::{
         CapabilityFlags, ConnectivityState, DeviceInterfaceFlags, DeviceState, DeviceStateReason,
         MeteredStatus,
     },
-    Error,
+    util::optional_path,
+    Dhcp4Config, Dhcp6Config, Error, Ip4Config, Ip6Config,
 };
 
 use super::Device;
@@ -37,15 +40,19 @@ impl Device {
     ///
     /// Reapply can make the _applied connection_ different from the _connection_, just like
     /// updating the _connection_ can make them different.
+    ///
+    /// The settings are checked with [`ConnectionSettings::validate()`] first.
     pub async fn reapply(
         &self,
-        connection: HashMap<&str, HashMap<&str, Value<'_>>>,
+        settings: &ConnectionSettings,
         version_id: u64,
         flags: u32,
     ) -> Result<(), Error> {
+        settings.validate()?;
+        let raw = settings.to_dbus()?;
         self.raw()
             .await?
-            .reapply(connection, version_id, flags)
+            .reapply(borrow_dbus(&raw)?, version_id, flags)
             .await
             .map_err(Error::ZBus)
     }
@@ -61,7 +68,10 @@ impl Device {
     /// _applied connection_ is set when activating a device or when calling reapply.
     pub async fn get_applied_connection(&self) -> Result<AppliedConnection, Error> {
         let (settings, version) = self.raw().await?.get_applied_connection(0).await?;
-        Ok(AppliedConnection { settings, version })
+        Ok(AppliedConnection {
+            settings: ConnectionSettings::from_dbus(settings)?,
+            version,
+        })
     }
 
     /// Disconnects a device and prevents the device from automatically activating further connections without user intervention.
@@ -156,29 +166,129 @@ impl Device {
         ))
     }
 
-    // pub async fn active_connection(&self) -> Result<Connection, Error> {
-    //     todo!()
-    // }
+    /// Subscribe to the state transitions of the device.
+    ///
+    /// The stream yields every change of [`Device::state()`], along with the previous state and
+    /// the reason for the change. Transitions that occurred before this method was called are not
+    /// reported.
+    pub async fn state_changes(
+        &self,
+    ) -> Result<impl Stream<Item = Result<DeviceStateTransition, Error>> + Unpin, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .receive_state_changed()
+            .await?
+            .map(|signal| {
+                let args = signal.args()?;
+                Ok(DeviceStateTransition {
+                    old: FromPrimitive::from_u32(args.old_state).ok_or(Error::UnsupportedType)?,
+                    new: FromPrimitive::from_u32(args.new_state).ok_or(Error::UnsupportedType)?,
+                    reason: FromPrimitive::from_u32(args.reason).ok_or(Error::UnsupportedType)?,
+                })
+            }))
+    }
+
+    /// Wait until the device reaches a state matching the predicate.
+    ///
+    /// If the device is already in a matching state, this returns immediately. Otherwise, it waits
+    /// for the device to transition into a matching state, and returns that state. If no matching
+    /// state is reached within `timeout`, this fails with [`Error::Timeout`].
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use passcod_networkmanager::{device::Device, types::DeviceState, Error};
+    /// # async fn example(device: Device) -> Result<(), Error> {
+    /// let state = device
+    ///     .wait_for_state(
+    ///         |state| matches!(state, DeviceState::Activated | DeviceState::Failed),
+    ///         Duration::from_secs(30),
+    ///     )
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn wait_for_state(
+        &self,
+        predicate: impl Fn(DeviceState) -> bool,
+        timeout: Duration,
+    ) -> Result<DeviceState, Error> {
+        // subscribe before reading the current state so no transition is missed in between
+        let mut changes = self.state_changes().await?;
+        crate::util::timeout(timeout, async {
+            let state = self.state().await?;
+            if predicate(state) {
+                return Ok(state);
+            }
+
+            while let Some(transition) = changes.next().await {
+                let state = transition?.new;
+                if predicate(state) {
+                    return Ok(state);
+                }
+            }
+
+            // the stream only ends if the bus connection goes away; let the timeout fire
+            std::future::pending().await
+        })
+        .await
+    }
 
-    pub async fn ip4_config(&self) -> Result<Ip4Config, Error> {
+    /// The active connection on this device, if any.
+    pub async fn active_connection(&self) -> Result<Option<ActiveConnection>, Error> {
+        let path = self.raw().await?.active_connection().await?;
+        Ok(optional_path(path).map(|path| ActiveConnection {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The IPv4 configuration of the device.
+    ///
+    /// This is only valid when the device is in the [`DeviceState::Activated`] state, and is None
+    /// when the device has no IPv4 configuration.
+    pub async fn ip4_config(&self) -> Result<Option<Ip4Config>, Error> {
         let path = self.raw().await?.ip4_config().await?;
-        Ok(Ip4Config {
+        Ok(optional_path(path).map(|path| Ip4Config {
             zbus: self.zbus.clone(),
             path,
-        })
+        }))
     }
 
-    // fn dhcp4_config(&self) -> Result<Dhcp4Config, Error> {
-    //     todo!()
-    // }
+    /// The DHCPv4 configuration of the device.
+    ///
+    /// This is only valid when the device is in the [`DeviceState::Activated`] state, and is None
+    /// if the device doesn't use DHCPv4.
+    pub async fn dhcp4_config(&self) -> Result<Option<Dhcp4Config>, Error> {
+        let path = self.raw().await?.dhcp4_config().await?;
+        Ok(optional_path(path).map(|path| Dhcp4Config {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
 
-    // fn ipv6_config(&self) -> Result<Ip6Config, Error> {
-    //     todo!()
-    // }
+    /// The IPv6 configuration of the device.
+    ///
+    /// This is only valid when the device is in the [`DeviceState::Activated`] state, and is None
+    /// when the device has no IPv6 configuration.
+    pub async fn ip6_config(&self) -> Result<Option<Ip6Config>, Error> {
+        let path = self.raw().await?.ip6_config().await?;
+        Ok(optional_path(path).map(|path| Ip6Config {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
 
-    // fn dhcp6_config(&self) -> Result<Dhcp6Config, Error> {
-    //     todo!()
-    // }
+    /// The DHCPv6 configuration of the device.
+    ///
+    /// This is only valid when the device is in the [`DeviceState::Activated`] state, and is None
+    /// if the device doesn't use DHCPv6.
+    pub async fn dhcp6_config(&self) -> Result<Option<Dhcp6Config>, Error> {
+        let path = self.raw().await?.dhcp6_config().await?;
+        Ok(optional_path(path).map(|path| Dhcp6Config {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
 
     /// Whether or not this device is managed by NetworkManager.
     pub async fn is_managed(&self) -> Result<bool, Error> {
@@ -247,8 +357,19 @@ impl Device {
     //     Ok(connections)
     // }
 
-    // pub async fn ports(&self) -> Result<Vec<Port>, Error> {
-    // }
+    /// The devices attached as ports of this device, if it is a controller like a bond or bridge.
+    pub async fn ports(&self) -> Result<impl Iterator<Item = Device> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .ports()
+            .await?
+            .into_iter()
+            .map(|path| Device {
+                zbus: self.zbus.clone(),
+                path,
+            }))
+    }
 
     /// An opaque indicator of the physical network port associated with the device.
     ///
@@ -329,7 +450,7 @@ impl Device {
 /// Obtained from [`Device::get_applied_connection()`].
 pub struct AppliedConnection {
     /// The effective connection settings that the connection has currently applied.
-    pub settings: HashMap<String, HashMap<String, OwnedValue>>,
+    pub settings: ConnectionSettings,
 
     /// The version ID of the currently applied connection.
     ///
@@ -338,3 +459,18 @@ pub struct AppliedConnection {
     /// modified in the meantime, the `version` gets incremented and the reapply will fail.
     pub version: u64,
 }
+
+/// A state transition of a device.
+///
+/// Obtained from [`Device::state_changes()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DeviceStateTransition {
+    /// The state the device was in before the transition.
+    pub old: DeviceState,
+
+    /// The state the device is now in.
+    pub new: DeviceState,
+
+    /// The reason for the transition.
+    pub reason: DeviceStateReason,
+}
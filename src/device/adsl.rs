@@ -0,0 +1,25 @@
+use crate::{raw::device_adsl::DeviceAdslProxy, Error};
+
+use super::Device;
+
+/// An ADSL modem device.
+#[derive(Clone, Debug)]
+pub struct AdslDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, AdslDevice, DeviceAdslProxy<'_>);
+
+impl AdslDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl AdslDevice {
+    /// Whether the device has a carrier.
+    pub async fn carrier(&self) -> Result<bool, Error> {
+        self.raw().await?.carrier().await.map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{raw::device_bluetooth::DeviceBluetoothProxy, types::BluetoothCapabilityFlags, Error};
+
+use super::Device;
+
+/// A Bluetooth device providing network access, via DUN or NAP.
+#[derive(Clone, Debug)]
+pub struct BluetoothDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, BluetoothDevice, DeviceBluetoothProxy<'_>);
+
+impl BluetoothDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl BluetoothDevice {
+    /// The name of the Bluetooth device.
+    pub async fn name(&self) -> Result<String, Error> {
+        self.raw().await?.name().await.map_err(Error::ZBus)
+    }
+
+    /// The network capabilities of the Bluetooth device.
+    pub async fn capabilities(&self) -> Result<BluetoothCapabilityFlags, Error> {
+        let value = self.raw().await?.bt_capabilities().await?;
+        Ok(BluetoothCapabilityFlags::from_bits_retain(value))
+    }
+}
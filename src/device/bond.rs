@@ -0,0 +1,39 @@
+use crate::{raw::device_bond::DeviceBondProxy, Error};
+
+use super::Device;
+
+/// A bond device, aggregating several devices into one link.
+#[derive(Clone, Debug)]
+pub struct BondDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, BondDevice, DeviceBondProxy<'_>);
+
+impl BondDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl BondDevice {
+    /// Whether the device has a carrier.
+    pub async fn carrier(&self) -> Result<bool, Error> {
+        self.raw().await?.carrier().await.map_err(Error::ZBus)
+    }
+
+    /// The devices currently attached as ports of the bond.
+    pub async fn ports(&self) -> Result<impl Iterator<Item = Device> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .slaves()
+            .await?
+            .into_iter()
+            .map(|path| Device {
+                zbus: self.parent.zbus.clone(),
+                path,
+            }))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{raw::device_bridge::DeviceBridgeProxy, Error};
+
+use super::Device;
+
+/// A bridge device, forwarding traffic between the devices attached to it.
+#[derive(Clone, Debug)]
+pub struct BridgeDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, BridgeDevice, DeviceBridgeProxy<'_>);
+
+impl BridgeDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl BridgeDevice {
+    /// Whether the device has a carrier.
+    pub async fn carrier(&self) -> Result<bool, Error> {
+        self.raw().await?.carrier().await.map_err(Error::ZBus)
+    }
+
+    /// The devices currently attached as ports of the bridge.
+    pub async fn ports(&self) -> Result<impl Iterator<Item = Device> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .slaves()
+            .await?
+            .into_iter()
+            .map(|path| Device {
+                zbus: self.parent.zbus.clone(),
+                path,
+            }))
+    }
+}
@@ -0,0 +1,18 @@
+use super::Device;
+
+/// A dummy device, which drops all traffic sent to it.
+#[derive(Clone, Debug)]
+pub struct DummyDevice {
+    pub(crate) parent: Device,
+}
+
+impl DummyDevice {
+    pub(crate) fn new(parent: Device) -> Self {
+        Self { parent }
+    }
+
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
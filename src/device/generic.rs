@@ -0,0 +1,29 @@
+use crate::{raw::device_generic::DeviceGenericProxy, Error};
+
+use super::Device;
+
+/// A device of a type not otherwise supported by NetworkManager.
+#[derive(Clone, Debug)]
+pub struct GenericDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, GenericDevice, DeviceGenericProxy<'_>);
+
+impl GenericDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl GenericDevice {
+    /// A description of the type of the device, as reported by the kernel.
+    pub async fn type_description(&self) -> Result<String, Error> {
+        self.raw()
+            .await?
+            .type_description()
+            .await
+            .map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{raw::device_infiniband::DeviceInfinibandProxy, Error};
+
+use super::Device;
+
+/// An InfiniBand device.
+#[derive(Clone, Debug)]
+pub struct InfinibandDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, InfinibandDevice, DeviceInfinibandProxy<'_>);
+
+impl InfinibandDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl InfinibandDevice {
+    /// Whether the device has a carrier.
+    pub async fn carrier(&self) -> Result<bool, Error> {
+        self.raw().await?.carrier().await.map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,104 @@
+use num_traits::FromPrimitive;
+
+use crate::{
+    raw::device_iptunnel::DeviceIPTunnelProxy, types::IpTunnelMode, util::optional_path, Error,
+};
+
+use super::Device;
+
+/// An IP tunnel device.
+#[derive(Clone, Debug)]
+pub struct IpTunnelDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, IpTunnelDevice, DeviceIPTunnelProxy<'_>);
+
+impl IpTunnelDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl IpTunnelDevice {
+    /// The tunneling mode.
+    pub async fn mode(&self) -> Result<IpTunnelMode, Error> {
+        let value = self.raw().await?.mode().await?;
+        FromPrimitive::from_u32(value).ok_or(Error::UnsupportedType)
+    }
+
+    /// The device the tunnel traffic is sent through, if the tunnel is bound to one.
+    pub async fn parent(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.parent().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.parent.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The local endpoint of the tunnel.
+    pub async fn local(&self) -> Result<String, Error> {
+        self.raw().await?.local().await.map_err(Error::ZBus)
+    }
+
+    /// The remote endpoint of the tunnel.
+    pub async fn remote(&self) -> Result<String, Error> {
+        self.raw().await?.remote().await.map_err(Error::ZBus)
+    }
+
+    /// The TTL assigned to tunneled packets.
+    ///
+    /// A value of 0 means the TTL is inherited from the inner packet.
+    pub async fn ttl(&self) -> Result<u8, Error> {
+        self.raw().await?.ttl().await.map_err(Error::ZBus)
+    }
+
+    /// The type of service (IPv4) or traffic class (IPv6) assigned to tunneled packets.
+    pub async fn tos(&self) -> Result<u8, Error> {
+        self.raw().await?.tos().await.map_err(Error::ZBus)
+    }
+
+    /// Whether path MTU discovery is enabled on the tunnel.
+    pub async fn path_mtu_discovery(&self) -> Result<bool, Error> {
+        self.raw()
+            .await?
+            .path_mtu_discovery()
+            .await
+            .map_err(Error::ZBus)
+    }
+
+    /// The key used for tunnel input packets, for GRE tunnels.
+    pub async fn input_key(&self) -> Result<String, Error> {
+        self.raw().await?.input_key().await.map_err(Error::ZBus)
+    }
+
+    /// The key used for tunnel output packets, for GRE tunnels.
+    pub async fn output_key(&self) -> Result<String, Error> {
+        self.raw().await?.output_key().await.map_err(Error::ZBus)
+    }
+
+    /// How many additional levels of encapsulation are permitted, for IPv6 tunnels.
+    pub async fn encapsulation_limit(&self) -> Result<u8, Error> {
+        self.raw()
+            .await?
+            .encapsulation_limit()
+            .await
+            .map_err(Error::ZBus)
+    }
+
+    /// The flow label assigned to tunnel packets, for IPv6 tunnels.
+    pub async fn flow_label(&self) -> Result<u32, Error> {
+        self.raw().await?.flow_label().await.map_err(Error::ZBus)
+    }
+
+    /// The firewall mark set on tunnel packets.
+    pub async fn fwmark(&self) -> Result<u32, Error> {
+        self.raw().await?.fw_mark().await.map_err(Error::ZBus)
+    }
+
+    /// The tunnel flags, as the `NMIPTunnelFlags` bits.
+    pub async fn flags(&self) -> Result<u32, Error> {
+        self.raw().await?.flags().await.map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,18 @@
+use super::Device;
+
+/// The loopback device.
+#[derive(Clone, Debug)]
+pub struct LoopbackDevice {
+    pub(crate) parent: Device,
+}
+
+impl LoopbackDevice {
+    pub(crate) fn new(parent: Device) -> Self {
+        Self { parent }
+    }
+
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
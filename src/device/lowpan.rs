@@ -0,0 +1,29 @@
+use crate::{raw::device_lowpan::DeviceLowpanProxy, util::optional_path, Error};
+
+use super::Device;
+
+/// A 6LoWPAN device.
+#[derive(Clone, Debug)]
+pub struct LowpanDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, LowpanDevice, DeviceLowpanProxy<'_>);
+
+impl LowpanDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl LowpanDevice {
+    /// The IEEE 802.15.4 device the 6LoWPAN device runs on.
+    pub async fn parent(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.parent().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.parent.zbus.clone(),
+            path,
+        }))
+    }
+}
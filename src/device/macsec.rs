@@ -0,0 +1,93 @@
+use crate::{raw::device_macsec::DeviceMacsecProxy, util::optional_path, Error};
+
+use super::Device;
+
+/// A MACsec device.
+#[derive(Clone, Debug)]
+pub struct MacsecDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, MacsecDevice, DeviceMacsecProxy<'_>);
+
+impl MacsecDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl MacsecDevice {
+    /// The device the MACsec device runs on.
+    pub async fn parent(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.parent().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.parent.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The Secure Channel Identifier in use.
+    pub async fn sci(&self) -> Result<u64, Error> {
+        self.raw().await?.sci().await.map_err(Error::ZBus)
+    }
+
+    /// The length of the Integrity Check Value, in bytes.
+    pub async fn icv_length(&self) -> Result<u8, Error> {
+        self.raw().await?.icv_length().await.map_err(Error::ZBus)
+    }
+
+    /// The cipher suite used for encryption.
+    pub async fn cipher_suite(&self) -> Result<u64, Error> {
+        self.raw().await?.cipher_suite().await.map_err(Error::ZBus)
+    }
+
+    /// The size of the replay window.
+    pub async fn window(&self) -> Result<u32, Error> {
+        self.raw().await?.window().await.map_err(Error::ZBus)
+    }
+
+    /// The currently active Secure Association used for encoding.
+    pub async fn encoding_sa(&self) -> Result<u8, Error> {
+        self.raw().await?.encoding_sa().await.map_err(Error::ZBus)
+    }
+
+    /// The validation mode for incoming packets: `strict`, `check` or `disabled`.
+    pub async fn validation(&self) -> Result<String, Error> {
+        self.raw().await?.validation().await.map_err(Error::ZBus)
+    }
+
+    /// Whether encryption of transmitted frames is enabled.
+    pub async fn encrypt(&self) -> Result<bool, Error> {
+        self.raw().await?.encrypt().await.map_err(Error::ZBus)
+    }
+
+    /// Whether the authenticity of transmitted frames is protected.
+    pub async fn protect(&self) -> Result<bool, Error> {
+        self.raw().await?.protect().await.map_err(Error::ZBus)
+    }
+
+    /// Whether the SCI is always included in transmitted frames.
+    pub async fn include_sci(&self) -> Result<bool, Error> {
+        self.raw().await?.include_sci().await.map_err(Error::ZBus)
+    }
+
+    /// Whether the End Station bit is set in transmitted frames.
+    pub async fn es(&self) -> Result<bool, Error> {
+        self.raw().await?.es().await.map_err(Error::ZBus)
+    }
+
+    /// Whether the Single Copy Broadcast bit is set in transmitted frames.
+    pub async fn scb(&self) -> Result<bool, Error> {
+        self.raw().await?.scb().await.map_err(Error::ZBus)
+    }
+
+    /// Whether replay protection is enabled.
+    pub async fn replay_protect(&self) -> Result<bool, Error> {
+        self.raw()
+            .await?
+            .replay_protect()
+            .await
+            .map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{raw::device_macvlan::DeviceMacvlanProxy, util::optional_path, Error};
+
+use super::Device;
+
+/// A MACVLAN or MACVTAP device.
+#[derive(Clone, Debug)]
+pub struct MacvlanDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, MacvlanDevice, DeviceMacvlanProxy<'_>);
+
+impl MacvlanDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl MacvlanDevice {
+    /// The device the MACVLAN device runs on.
+    pub async fn parent(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.parent().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.parent.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The MACVLAN mode: `vepa`, `bridge`, `private` or `passthru`.
+    pub async fn mode(&self) -> Result<String, Error> {
+        self.raw().await?.mode().await.map_err(Error::ZBus)
+    }
+
+    /// Whether the parent device is kept out of promiscuous mode.
+    pub async fn no_promisc(&self) -> Result<bool, Error> {
+        self.raw().await?.no_promisc().await.map_err(Error::ZBus)
+    }
+
+    /// Whether the device is a MACVTAP rather than a MACVLAN.
+    pub async fn tap(&self) -> Result<bool, Error> {
+        self.raw().await?.tap().await.map_err(Error::ZBus)
+    }
+}
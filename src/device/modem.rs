@@ -0,0 +1,47 @@
+use crate::{raw::device_modem::DeviceModemProxy, types::ModemCapabilityFlags, Error};
+
+use super::Device;
+
+/// A mobile broadband modem.
+#[derive(Clone, Debug)]
+pub struct ModemDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, ModemDevice, DeviceModemProxy<'_>);
+
+impl ModemDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl ModemDevice {
+    /// The generic network capabilities of the modem.
+    pub async fn modem_capabilities(&self) -> Result<ModemCapabilityFlags, Error> {
+        let value = self.raw().await?.modem_capabilities().await?;
+        Ok(ModemCapabilityFlags::from_bits_retain(value))
+    }
+
+    /// The network capabilities the modem currently uses, which may change after a firmware reload.
+    pub async fn current_capabilities(&self) -> Result<ModemCapabilityFlags, Error> {
+        let value = self.raw().await?.current_capabilities().await?;
+        Ok(ModemCapabilityFlags::from_bits_retain(value))
+    }
+
+    /// An identifier of the modem hardware, as reported by ModemManager.
+    pub async fn device_id(&self) -> Result<String, Error> {
+        self.raw().await?.device_id().await.map_err(Error::ZBus)
+    }
+
+    /// The MCC and MNC of the network the modem is registered on.
+    pub async fn operator_code(&self) -> Result<String, Error> {
+        self.raw().await?.operator_code().await.map_err(Error::ZBus)
+    }
+
+    /// The access point name the modem is connected to.
+    pub async fn apn(&self) -> Result<String, Error> {
+        self.raw().await?.apn().await.map_err(Error::ZBus)
+    }
+}
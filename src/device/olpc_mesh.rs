@@ -0,0 +1,38 @@
+use crate::{raw::device_olpcmesh::DeviceOlpcMeshProxy, util::optional_path, Error};
+
+use super::Device;
+
+/// An OLPC XO mesh device.
+#[derive(Clone, Debug)]
+pub struct OlpcMeshDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, OlpcMeshDevice, DeviceOlpcMeshProxy<'_>);
+
+impl OlpcMeshDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl OlpcMeshDevice {
+    /// The Wi-Fi device the mesh device is paired with.
+    pub async fn companion(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.companion().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.parent.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The currently active channel.
+    pub async fn active_channel(&self) -> Result<u32, Error> {
+        self.raw()
+            .await?
+            .active_channel()
+            .await
+            .map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{raw::device_ovsbridge::DeviceOvsBridgeProxy, Error};
+
+use super::Device;
+
+/// An Open vSwitch bridge.
+#[derive(Clone, Debug)]
+pub struct OvsBridgeDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, OvsBridgeDevice, DeviceOvsBridgeProxy<'_>);
+
+impl OvsBridgeDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl OvsBridgeDevice {
+    /// The Open vSwitch ports attached to the bridge.
+    pub async fn ports(&self) -> Result<impl Iterator<Item = Device> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .slaves()
+            .await?
+            .into_iter()
+            .map(|path| Device {
+                zbus: self.parent.zbus.clone(),
+                path,
+            }))
+    }
+}
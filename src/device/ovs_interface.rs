@@ -0,0 +1,18 @@
+use super::Device;
+
+/// An Open vSwitch interface.
+#[derive(Clone, Debug)]
+pub struct OvsInterfaceDevice {
+    pub(crate) parent: Device,
+}
+
+impl OvsInterfaceDevice {
+    pub(crate) fn new(parent: Device) -> Self {
+        Self { parent }
+    }
+
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{raw::device_ovsport::DeviceOvsPortProxy, Error};
+
+use super::Device;
+
+/// An Open vSwitch port.
+#[derive(Clone, Debug)]
+pub struct OvsPortDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, OvsPortDevice, DeviceOvsPortProxy<'_>);
+
+impl OvsPortDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl OvsPortDevice {
+    /// The Open vSwitch interfaces attached to the port.
+    pub async fn interfaces(&self) -> Result<impl Iterator<Item = Device> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .slaves()
+            .await?
+            .into_iter()
+            .map(|path| Device {
+                zbus: self.parent.zbus.clone(),
+                path,
+            }))
+    }
+}
@@ -0,0 +1,18 @@
+use super::Device;
+
+/// A PPP device.
+#[derive(Clone, Debug)]
+pub struct PppDevice {
+    pub(crate) parent: Device,
+}
+
+impl PppDevice {
+    pub(crate) fn new(parent: Device) -> Self {
+        Self { parent }
+    }
+
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
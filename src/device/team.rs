@@ -0,0 +1,44 @@
+use crate::{raw::device_team::DeviceTeamProxy, Error};
+
+use super::Device;
+
+/// A team device, aggregating several devices into one link.
+#[derive(Clone, Debug)]
+pub struct TeamDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, TeamDevice, DeviceTeamProxy<'_>);
+
+impl TeamDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl TeamDevice {
+    /// Whether the device has a carrier.
+    pub async fn carrier(&self) -> Result<bool, Error> {
+        self.raw().await?.carrier().await.map_err(Error::ZBus)
+    }
+
+    /// The devices currently attached as ports of the team.
+    pub async fn ports(&self) -> Result<impl Iterator<Item = Device> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .slaves()
+            .await?
+            .into_iter()
+            .map(|path| Device {
+                zbus: self.parent.zbus.clone(),
+                path,
+            }))
+    }
+
+    /// The JSON configuration of the team.
+    pub async fn config(&self) -> Result<String, Error> {
+        self.raw().await?.config().await.map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{raw::device_tun::DeviceTunProxy, Error};
+
+use super::Device;
+
+/// A TUN or TAP device.
+#[derive(Clone, Debug)]
+pub struct TunDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, TunDevice, DeviceTunProxy<'_>);
+
+impl TunDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl TunDevice {
+    /// The mode of the device: `tun` or `tap`.
+    pub async fn mode(&self) -> Result<String, Error> {
+        self.raw().await?.mode().await.map_err(Error::ZBus)
+    }
+
+    /// The user ID owning the device, if any.
+    pub async fn owner(&self) -> Result<Option<u32>, Error> {
+        let id = self.raw().await?.owner().await?;
+        Ok(u32::try_from(id).ok())
+    }
+
+    /// The group ID owning the device, if any.
+    pub async fn group(&self) -> Result<Option<u32>, Error> {
+        let id = self.raw().await?.group().await?;
+        Ok(u32::try_from(id).ok())
+    }
+
+    /// Whether packets are sent without protocol information.
+    pub async fn no_pi(&self) -> Result<bool, Error> {
+        self.raw().await?.no_pi().await.map_err(Error::ZBus)
+    }
+
+    /// Whether packets are sent with a virtio network header.
+    pub async fn vnet_hdr(&self) -> Result<bool, Error> {
+        self.raw().await?.vnet_hdr().await.map_err(Error::ZBus)
+    }
+
+    /// Whether the device has multiple queues.
+    pub async fn multi_queue(&self) -> Result<bool, Error> {
+        self.raw().await?.multi_queue().await.map_err(Error::ZBus)
+    }
+}
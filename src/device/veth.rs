@@ -0,0 +1,29 @@
+use crate::{raw::device_veth::DeviceVethProxy, util::optional_path, Error};
+
+use super::Device;
+
+/// A virtual Ethernet pair device.
+#[derive(Clone, Debug)]
+pub struct VethDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, VethDevice, DeviceVethProxy<'_>);
+
+impl VethDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl VethDevice {
+    /// The other end of the pair.
+    pub async fn peer(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.peer().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.parent.zbus.clone(),
+            path,
+        }))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{raw::device_vlan::DeviceVlanProxy, util::optional_path, Error};
+
+use super::Device;
+
+/// A VLAN device.
+#[derive(Clone, Debug)]
+pub struct VlanDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, VlanDevice, DeviceVlanProxy<'_>);
+
+impl VlanDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl VlanDevice {
+    /// Whether the device has a carrier.
+    pub async fn carrier(&self) -> Result<bool, Error> {
+        self.raw().await?.carrier().await.map_err(Error::ZBus)
+    }
+
+    /// The device the VLAN runs on.
+    pub async fn parent(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.parent().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.parent.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The VLAN ID.
+    pub async fn vlan_id(&self) -> Result<u32, Error> {
+        self.raw().await?.vlan_id().await.map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{raw::device_vrf::DeviceVrfProxy, Error};
+
+use super::Device;
+
+/// A Virtual Routing and Forwarding device.
+#[derive(Clone, Debug)]
+pub struct VrfDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, VrfDevice, DeviceVrfProxy<'_>);
+
+impl VrfDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl VrfDevice {
+    /// The routing table of the VRF.
+    pub async fn table(&self) -> Result<u32, Error> {
+        self.raw().await?.table().await.map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{raw::device_vxlan::DeviceVxlanProxy, util::optional_path, Error};
+
+use super::Device;
+
+/// A VXLAN device.
+#[derive(Clone, Debug)]
+pub struct VxlanDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, VxlanDevice, DeviceVxlanProxy<'_>);
+
+impl VxlanDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl VxlanDevice {
+    /// The device the VXLAN sends its traffic through, if bound to one.
+    pub async fn parent(&self) -> Result<Option<Device>, Error> {
+        let path = self.raw().await?.parent().await?;
+        Ok(optional_path(path).map(|path| Device {
+            zbus: self.parent.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The VXLAN Network Identifier (VNI).
+    pub async fn id(&self) -> Result<u32, Error> {
+        self.raw().await?.id().await.map_err(Error::ZBus)
+    }
+
+    /// The multicast group, or the unicast destination, of the VXLAN.
+    pub async fn group(&self) -> Result<String, Error> {
+        self.raw().await?.group().await.map_err(Error::ZBus)
+    }
+
+    /// The local address used as source of the VXLAN traffic.
+    pub async fn local(&self) -> Result<String, Error> {
+        self.raw().await?.local().await.map_err(Error::ZBus)
+    }
+
+    /// The type of service assigned to outgoing packets.
+    pub async fn tos(&self) -> Result<u8, Error> {
+        self.raw().await?.tos().await.map_err(Error::ZBus)
+    }
+
+    /// The TTL assigned to outgoing packets.
+    pub async fn ttl(&self) -> Result<u8, Error> {
+        self.raw().await?.ttl().await.map_err(Error::ZBus)
+    }
+
+    /// Whether unknown source link layer addresses and IP addresses are added to the forwarding
+    /// database.
+    pub async fn learning(&self) -> Result<bool, Error> {
+        self.raw().await?.learning().await.map_err(Error::ZBus)
+    }
+
+    /// The lifetime of forwarding database entries, in seconds.
+    pub async fn ageing(&self) -> Result<u32, Error> {
+        self.raw().await?.ageing().await.map_err(Error::ZBus)
+    }
+
+    /// The maximum number of entries in the forwarding database.
+    pub async fn limit(&self) -> Result<u32, Error> {
+        self.raw().await?.limit().await.map_err(Error::ZBus)
+    }
+
+    /// The destination UDP port.
+    pub async fn dst_port(&self) -> Result<u16, Error> {
+        self.raw().await?.dst_port().await.map_err(Error::ZBus)
+    }
+
+    /// The lowest source UDP port.
+    pub async fn src_port_min(&self) -> Result<u16, Error> {
+        self.raw().await?.src_port_min().await.map_err(Error::ZBus)
+    }
+
+    /// The highest source UDP port.
+    pub async fn src_port_max(&self) -> Result<u16, Error> {
+        self.raw().await?.src_port_max().await.map_err(Error::ZBus)
+    }
+
+    /// Whether ARP proxy is enabled.
+    pub async fn proxy(&self) -> Result<bool, Error> {
+        self.raw().await?.proxy().await.map_err(Error::ZBus)
+    }
+
+    /// Whether route short circuit is enabled.
+    pub async fn rsc(&self) -> Result<bool, Error> {
+        self.raw().await?.rsc().await.map_err(Error::ZBus)
+    }
+
+    /// Whether netlink notifications are generated for link layer address misses.
+    pub async fn l2miss(&self) -> Result<bool, Error> {
+        self.raw().await?.l2miss().await.map_err(Error::ZBus)
+    }
+
+    /// Whether netlink notifications are generated for IP address misses.
+    pub async fn l3miss(&self) -> Result<bool, Error> {
+        self.raw().await?.l3miss().await.map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::{raw::device_wifip2p::DeviceWifiP2PProxy, Error};
+
+use super::Device;
+
+/// A Wi-Fi P2P (Wi-Fi Direct) device.
+#[derive(Clone, Debug)]
+pub struct WifiP2pDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, WifiP2pDevice, DeviceWifiP2PProxy<'_>);
+
+impl WifiP2pDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+
+    /// Start a search for Wi-Fi P2P peers.
+    ///
+    /// The search stops after `timeout` seconds, between 1 and 600, or after 30 seconds if None.
+    pub async fn start_find(&self, timeout: Option<i32>) -> Result<(), Error> {
+        let mut options = HashMap::new();
+        if let Some(timeout) = timeout {
+            options.insert("timeout", timeout.into());
+        }
+        self.raw()
+            .await?
+            .start_find(options)
+            .await
+            .map_err(Error::ZBus)
+    }
+
+    /// Stop a search for Wi-Fi P2P peers.
+    pub async fn stop_find(&self) -> Result<(), Error> {
+        self.raw().await?.stop_find().await.map_err(Error::ZBus)
+    }
+}
+
+impl WifiP2pDevice {
+    /// The object paths of the Wi-Fi P2P peers visible to this device.
+    pub async fn peers(&self) -> Result<Vec<OwnedObjectPath>, Error> {
+        self.raw().await?.peers().await.map_err(Error::ZBus)
+    }
+}
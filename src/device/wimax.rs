@@ -0,0 +1,64 @@
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::{raw::device_wimax::DeviceWiMaxProxy, util::optional_path, Error};
+
+use super::Device;
+
+/// A WiMAX device.
+///
+/// WiMAX is no longer supported by NetworkManager since version 1.2.
+#[derive(Clone, Debug)]
+pub struct WimaxDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, WimaxDevice, DeviceWiMaxProxy<'_>);
+
+impl WimaxDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
+
+impl WimaxDevice {
+    /// The object paths of the Network Service Providers visible to this device.
+    pub async fn nsps(&self) -> Result<Vec<OwnedObjectPath>, Error> {
+        self.raw().await?.nsps().await.map_err(Error::ZBus)
+    }
+
+    /// The object path of the Network Service Provider currently in use, if any.
+    pub async fn active_nsp(&self) -> Result<Option<OwnedObjectPath>, Error> {
+        let path = self.raw().await?.active_nsp().await?;
+        Ok(optional_path(path))
+    }
+
+    /// The center frequency of the current base station, in kHz.
+    pub async fn center_frequency(&self) -> Result<u32, Error> {
+        self.raw()
+            .await?
+            .center_frequency()
+            .await
+            .map_err(Error::ZBus)
+    }
+
+    /// The received signal strength indicator of the current base station, in dBm.
+    pub async fn rssi(&self) -> Result<i32, Error> {
+        self.raw().await?.rssi().await.map_err(Error::ZBus)
+    }
+
+    /// The carrier to interference and noise ratio of the current base station, in dB.
+    pub async fn cinr(&self) -> Result<i32, Error> {
+        self.raw().await?.cinr().await.map_err(Error::ZBus)
+    }
+
+    /// The average power of the last burst transmitted by the device, in units of 0.5 dBm.
+    pub async fn tx_power(&self) -> Result<i32, Error> {
+        self.raw().await?.tx_power().await.map_err(Error::ZBus)
+    }
+
+    /// The ID of the current base station.
+    pub async fn bsid(&self) -> Result<String, Error> {
+        self.raw().await?.bsid().await.map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,63 @@
+use futures_lite::Stream;
+
+use crate::{raw::device_wired::DeviceWiredProxy, Error};
+
+use super::Device;
+
+/// A wired Ethernet device.
+#[derive(Clone, Debug)]
+pub struct WiredDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, WiredDevice, DeviceWiredProxy<'_>);
+
+impl WiredDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+
+    /// Subscribe to changes of the carrier, ie a cable being plugged in or unplugged.
+    ///
+    /// The stream yields whether the device has a carrier each time it changes. Changes that
+    /// occurred before this method was called are not reported.
+    pub async fn carrier_changes(
+        &self,
+    ) -> Result<impl Stream<Item = Result<bool, Error>> + Unpin, Error> {
+        crate::util::property_changes(self.raw().await?.inner(), "Carrier").await
+    }
+}
+
+impl WiredDevice {
+    /// Whether the device has a carrier, ie a cable is plugged in and the link is up.
+    pub async fn carrier(&self) -> Result<bool, Error> {
+        self.raw().await?.carrier().await.map_err(Error::ZBus)
+    }
+
+    /// The permanent hardware address of the device.
+    pub async fn permanent_hardware_address(&self) -> Result<String, Error> {
+        self.raw()
+            .await?
+            .perm_hw_address()
+            .await
+            .map_err(Error::ZBus)
+    }
+
+    /// The speed of the link, in megabits/second (Mb/s).
+    ///
+    /// A value of None means the speed is unknown, for example because there is no carrier.
+    pub async fn speed(&self) -> Result<Option<u32>, Error> {
+        let speed = self.raw().await?.speed().await?;
+        Ok(if speed == 0 { None } else { Some(speed) })
+    }
+
+    /// The IBM S/390 subchannels of the device, on that platform.
+    pub async fn s390_subchannels(&self) -> Result<Vec<String>, Error> {
+        self.raw()
+            .await?
+            .s390subchannels()
+            .await
+            .map_err(Error::ZBus)
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    raw::device_wireguard::DeviceWireGuardProxy,
+    setting::{Setting, WireGuardPeer, WireGuardPublicKey, WireGuardSetting},
+    Error,
+};
+
+use super::Device;
+
+/// A WireGuard device.
+#[derive(Clone, Debug)]
+pub struct WireGuardDevice {
+    pub(crate) parent: Device,
+}
+
+crate::zproxy_sub!(Device, WireGuardDevice, DeviceWireGuardProxy<'_>);
+
+impl WireGuardDevice {
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+
+    /// Add a peer to the running tunnel, or replace the peer with the same public key.
+    ///
+    /// The peer is added to the _applied connection_ with [`Device::reapply()`], so established
+    /// sessions with the other peers are not interrupted. The connection profile is not changed:
+    /// update it too for the peer to persist when the tunnel is next activated.
+    ///
+    /// As the applied connection has no secrets, this fetches the private key and preshared keys
+    /// from the profile to reapply them, and fails if they are not available to the caller.
+    pub async fn add_peer(&self, peer: WireGuardPeer) -> Result<(), Error> {
+        self.update_peers(|wireguard| {
+            wireguard.add_peer(peer);
+            true
+        })
+        .await
+        .map(drop)
+    }
+
+    /// Remove a peer from the running tunnel.
+    ///
+    /// Like [`WireGuardDevice::add_peer()`], this only changes the _applied connection_. Returns
+    /// false if there was no such peer, in which case nothing is reapplied.
+    pub async fn remove_peer(&self, public_key: &WireGuardPublicKey) -> Result<bool, Error> {
+        self.update_peers(|wireguard| wireguard.remove_peer(public_key).is_some())
+            .await
+    }
+
+    async fn update_peers(
+        &self,
+        update: impl FnOnce(&mut WireGuardSetting) -> bool,
+    ) -> Result<bool, Error> {
+        let mut applied = self.parent.get_applied_connection().await?;
+        let wireguard = applied.settings.wireguard.as_mut().ok_or_else(|| {
+            Error::InvalidSetting(format!(
+                "the applied connection has no {} setting",
+                WireGuardSetting::NAME
+            ))
+        })?;
+
+        // the applied connection has no secrets, and reapplying it as is would drop the keys
+        let active = self.parent.active_connection().await?.ok_or_else(|| {
+            Error::InvalidSetting(format!(
+                "the device has no active connection to read the {} secrets from",
+                WireGuardSetting::NAME
+            ))
+        })?;
+        let secrets = active
+            .connection()
+            .await?
+            .secrets_for_setting(WireGuardSetting::NAME)
+            .await?;
+        if let Some(secrets) = secrets.wireguard {
+            wireguard.merge_secrets(secrets);
+        }
+
+        if !update(wireguard) {
+            return Ok(false);
+        }
+
+        wireguard.validate()?;
+        // the version makes the reapply fail if the applied connection changed in the meantime
+        self.parent
+            .reapply(&applied.settings, applied.version, 0)
+            .await?;
+        Ok(true)
+    }
+}
+
+impl WireGuardDevice {
+    /// The public key of the device.
+    ///
+    /// A value of None means the device has no private key yet.
+    pub async fn public_key(&self) -> Result<Option<WireGuardPublicKey>, Error> {
+        let key = self.raw().await?.public_key().await?;
+        if key.is_empty() {
+            return Ok(None);
+        }
+
+        let key = <[u8; 32]>::try_from(key).map_err(|_| Error::UnsupportedType)?;
+        Ok(Some(WireGuardPublicKey::from_bytes(key)))
+    }
+
+    /// The UDP port the device listens on.
+    pub async fn listen_port(&self) -> Result<u16, Error> {
+        self.raw().await?.listen_port().await.map_err(Error::ZBus)
+    }
+
+    /// The firewall mark set on outgoing packets.
+    ///
+    /// A value of 0 means no mark is set.
+    pub async fn fwmark(&self) -> Result<u32, Error> {
+        self.raw().await?.fw_mark().await.map_err(Error::ZBus)
+    }
+}
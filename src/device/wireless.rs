@@ -1,16 +1,36 @@
+use std::{collections::HashMap, time::Duration};
+
+use futures_lite::{Stream, StreamExt};
+use num_traits::FromPrimitive;
+use zbus::zvariant::OwnedObjectPath;
+
 use crate::{
+    active_connection::ActiveConnection,
     raw::device_wireless::DeviceWirelessProxy,
-    types::{WirelessClientCapabilityFlags, WirelessClientMode},
-    Error,
+    setting::{
+        ConnectionSetting, ConnectionSettings, Ieee8021xSetting, Ipv4Method, Ipv4Setting,
+        KeyManagement, Setting, WirelessBand, WirelessMode, WirelessSecuritySetting,
+        WirelessSetting,
+    },
+    types::{BindActivation, PersistMode, WirelessClientCapabilityFlags, WirelessClientMode},
+    AddAndActivateOptions, Error, NetworkManager,
 };
 
 use super::Device;
 
 #[doc(inline)]
-pub use access_point::AccessPoint;
-use num_traits::FromPrimitive;
+pub use access_point::{AccessPoint, AccessPointSecurity};
+#[doc(inline)]
+pub use frequency::{channel_from_frequency, is_dfs_frequency, FrequencyBand};
+#[doc(inline)]
+pub use hotspot::Hotspot;
+#[doc(inline)]
+pub use scan::{ScanResult, ScannedAccessPoint};
 
 mod access_point;
+mod frequency;
+mod hotspot;
+mod scan;
 
 /// A Wi-Fi device.
 #[derive(Clone, Debug)]
@@ -44,28 +64,374 @@ impl WirelessDevice {
             }))
     }
 
+    /// Subscribe to access points appearing and disappearing.
+    ///
+    /// Events that occurred before this method was called are not reported.
+    pub async fn access_point_events(
+        &self,
+    ) -> Result<impl Stream<Item = Result<AccessPointEvent, Error>> + Unpin, Error> {
+        let raw = self.raw().await?;
+
+        let zbus = self.parent.zbus.clone();
+        let added = raw.receive_access_point_added().await?.map(move |signal| {
+            Ok(AccessPointEvent::Added(AccessPoint {
+                zbus: zbus.clone(),
+                path: signal.args()?.access_point.into(),
+            }))
+        });
+
+        let removed = raw.receive_access_point_removed().await?.map(|signal| {
+            Ok(AccessPointEvent::Removed(
+                signal.args()?.access_point.into(),
+            ))
+        });
+
+        Ok(added.or(removed))
+    }
+
     /// Request a new scan for access points.
+    ///
+    /// This returns immediately, before the scan is finished. See [`WirelessDevice::scan()`] to
+    /// wait for the results.
     pub async fn request_scan(&self) -> Result<(), Error> {
         self.raw()
             .await?
-            .request_scan(std::collections::HashMap::new())
-            .await?;
+            .request_scan(HashMap::new())
+            .await
+            .map_err(scan_error)?;
         Ok(())
     }
 
+    /// Scan for access points and wait for the results.
+    ///
+    /// This requests a scan, waits up to `timeout` for it to finish, and then returns the visible
+    /// networks as with [`WirelessDevice::scan_results()`].
+    ///
+    /// NetworkManager may refuse to scan again right after a previous scan, in which case this
+    /// fails with [`Error::ScanRateLimited`] and [`scan_results()`](Self::scan_results()) can be
+    /// used to get the results of the previous scan instead.
+    pub async fn scan(&self, timeout: Duration) -> Result<Vec<ScanResult>, Error> {
+        let raw = self.raw().await?;
+        // subscribe before requesting the scan so the change is not missed
+        let mut changes = raw.receive_last_scan_changed().await;
+        let before = raw.last_scan().await?;
+        raw.request_scan(HashMap::new()).await.map_err(scan_error)?;
+
+        crate::util::timeout(timeout, async {
+            while let Some(change) = changes.next().await {
+                if change.get().await? != before {
+                    return Ok(());
+                }
+            }
+
+            // the stream only ends if the bus connection goes away; let the timeout fire
+            std::future::pending().await
+        })
+        .await?;
+
+        self.scan_results().await
+    }
+
+    /// Get the networks currently visible to this device, from the last scan.
+    ///
+    /// Access points are grouped by SSID, and the networks are sorted by the signal strength of
+    /// their strongest access point, strongest first. Access points that disappear while their
+    /// properties are read are left out.
+    pub async fn scan_results(&self) -> Result<Vec<ScanResult>, Error> {
+        let mut access_points = Vec::new();
+        for ap in self.get_all_access_points().await? {
+            if let Some(ap) = ScannedAccessPoint::new(ap).await? {
+                access_points.push(ap);
+            }
+        }
+        Ok(ScanResult::group(access_points))
+    }
+
     /// Request a new scan for access points with the given SSIDs.
     pub async fn request_scan_with_ssids(&self, ssids: Vec<Vec<u8>>) -> Result<(), Error> {
         self.raw()
             .await?
-            .request_scan(std::collections::HashMap::from_iter([(
-                "ssids",
-                ssids.into(),
-            )]))
-            .await?;
+            .request_scan(HashMap::from_iter([("ssids", ssids.into())]))
+            .await
+            .map_err(scan_error)?;
         Ok(())
     }
 }
 
+impl WirelessDevice {
+    /// Connect to the Wi-Fi network with the given SSID.
+    ///
+    /// This picks the strongest access point visible with that SSID, and uses its security flags
+    /// to choose the key management. If a saved profile for the SSID exists, it is activated,
+    /// after updating its key management and password if one is given; its other security
+    /// settings, like PMF, are kept. Otherwise a new profile is created. Then this
+    /// waits up to `timeout` for the connection to be activated, see
+    /// [`ActiveConnection::wait_activated()`].
+    ///
+    /// Fails with [`Error::AccessPointNotFound`] if no access point with the SSID is visible, in
+    /// which case [requesting a scan](Self::request_scan_with_ssids()) may help. Enterprise
+    /// networks are not supported, as they need more than a password: create their profile with an
+    /// [`Ieee8021xSetting`](crate::setting::Ieee8021xSetting) instead.
+    ///
+    /// ```rust,no_run
+    /// # use std::{collections::HashMap, time::Duration};
+    /// # use passcod_networkmanager::{device::wireless::WirelessDevice, Error};
+    /// # async fn example(wifi: WirelessDevice) -> Result<(), Error> {
+    /// wifi.connect("Home", Some("correct horse battery staple"), Duration::from_secs(30))
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn connect(
+        &self,
+        ssid: impl AsRef<[u8]>,
+        password: Option<&str>,
+        timeout: Duration,
+    ) -> Result<ActiveConnection, Error> {
+        let ssid = ssid.as_ref();
+
+        let mut best: Option<ScannedAccessPoint> = None;
+        for ap in self.get_all_access_points().await? {
+            let Some((ap_ssid, ap)) = ScannedAccessPoint::new(ap).await? else {
+                continue;
+            };
+            if ap_ssid != ssid {
+                continue;
+            }
+            let stronger = match &best {
+                Some(best) => ap.strength > best.strength,
+                None => true,
+            };
+            if stronger {
+                best = Some(ap);
+            }
+        }
+        let ap = best.ok_or(Error::AccessPointNotFound)?;
+        let security = security_for(ap.security, password)?;
+
+        let nm = NetworkManager::new_with_zbus(self.parent.zbus.clone());
+        for profile in nm.settings().list_connections().await? {
+            // profiles that can't be read, like one deleted since it was listed, are not candidates
+            let Ok(mut settings) = profile.settings().await else {
+                continue;
+            };
+            let Some(wireless) = &settings.wireless else {
+                continue;
+            };
+            if wireless.ssid.as_deref() != Some(ssid)
+                || wireless
+                    .mode
+                    .is_some_and(|mode| mode != WirelessMode::Infrastructure)
+            {
+                continue;
+            }
+
+            if password.is_some() {
+                update_security(&mut settings.wireless_security, security);
+                profile.update(&settings).await?;
+            }
+            return nm
+                .activate_connection(&profile, Some(&self.parent), Some(&ap.access_point.path))
+                .await?
+                .wait_activated(timeout)
+                .await;
+        }
+
+        let settings = ConnectionSettings {
+            connection: Some(ConnectionSetting {
+                id: Some(String::from_utf8_lossy(ssid).into_owned()),
+                connection_type: Some(WirelessSetting::NAME.into()),
+                ..Default::default()
+            }),
+            wireless: Some(WirelessSetting::new(ssid)?.with_mode(WirelessMode::Infrastructure)),
+            wireless_security: security,
+            ..Default::default()
+        };
+        let (_, active) = nm
+            .add_and_activate_connection(
+                &settings,
+                Some(&self.parent),
+                Some(&ap.access_point.path),
+                AddAndActivateOptions::default(),
+            )
+            .await?;
+        active.wait_activated(timeout).await
+    }
+}
+
+impl WirelessDevice {
+    /// Start a WPA2 hotspot with the given SSID and password.
+    ///
+    /// The `band` restricts the hotspot to 2.4 or 5 GHz; if None, NetworkManager picks one. See
+    /// [`WirelessDevice::start_hotspot_with_security()`] for the details, and to use other
+    /// security settings like WPA3.
+    pub async fn start_hotspot(
+        &self,
+        ssid: impl AsRef<[u8]>,
+        password: &str,
+        band: Option<WirelessBand>,
+        timeout: Duration,
+    ) -> Result<Hotspot, Error> {
+        let security = WirelessSecuritySetting {
+            proto: Some(vec!["rsn".into()]),
+            pairwise: Some(vec!["ccmp".into()]),
+            group: Some(vec!["ccmp".into()]),
+            ..WirelessSecuritySetting::wpa_psk(password)?
+        };
+        self.start_hotspot_with_security(ssid, security, band, timeout)
+            .await
+    }
+
+    /// Start a hotspot with the given SSID and security settings.
+    ///
+    /// This deactivates the current connection of the device, and creates a volatile profile for
+    /// the hotspot that is deleted when it is stopped, or when the returned [`Hotspot`] is dropped. Clients get addresses from NetworkManager's
+    /// DHCP server, and their traffic is shared through NAT with the other connections of the
+    /// machine. This waits up to `timeout` for the hotspot to be up, see
+    /// [`ActiveConnection::wait_activated()`].
+    ///
+    /// Fails with [`Error::UnsupportedDevice`] if the device cannot act as an access point.
+    ///
+    /// ```rust,no_run
+    /// # use std::{collections::HashMap, time::Duration};
+    /// # use passcod_networkmanager::{device::wireless::WirelessDevice, setting::WirelessSecuritySetting, Error};
+    /// # async fn example(wifi: WirelessDevice) -> Result<(), Error> {
+    /// let security = WirelessSecuritySetting::sae("correct horse battery staple")?;
+    /// let hotspot = wifi
+    ///     .start_hotspot_with_security("Setup", security, None, Duration::from_secs(30))
+    ///     .await?;
+    /// // ...
+    /// hotspot.stop().await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn start_hotspot_with_security(
+        &self,
+        ssid: impl AsRef<[u8]>,
+        security: WirelessSecuritySetting,
+        band: Option<WirelessBand>,
+        timeout: Duration,
+    ) -> Result<Hotspot, Error> {
+        if !self
+            .capabilities()
+            .await?
+            .contains(WirelessClientCapabilityFlags::AP)
+        {
+            return Err(Error::UnsupportedDevice);
+        }
+
+        let ssid = ssid.as_ref();
+        let mut wireless = WirelessSetting::new(ssid)?.with_mode(WirelessMode::Ap);
+        wireless.band = band;
+        let settings = ConnectionSettings {
+            connection: Some(ConnectionSetting {
+                id: Some(format!("Hotspot {}", String::from_utf8_lossy(ssid))),
+                connection_type: Some(WirelessSetting::NAME.into()),
+                autoconnect: Some(false),
+                ..Default::default()
+            }),
+            wireless: Some(wireless),
+            wireless_security: Some(security),
+            ipv4: Some(Ipv4Setting::new(Ipv4Method::Shared)),
+            ..Default::default()
+        };
+        let previous = match self.parent.active_connection().await? {
+            Some(active) => Some(active.connection().await?),
+            None => None,
+        };
+
+        let nm = NetworkManager::new_with_zbus(self.parent.zbus.clone());
+        let (_, active) = nm
+            .add_and_activate_connection(
+                &settings,
+                Some(&self.parent),
+                None,
+                AddAndActivateOptions {
+                    persist: Some(PersistMode::Volatile),
+                    bind_activation: Some(BindActivation::DbusClient),
+                },
+            )
+            .await?;
+        let active = active.wait_activated(timeout).await?;
+        Ok(Hotspot::new(active, self.parent.clone(), previous))
+    }
+}
+
+/// Map the error NetworkManager returns when scans are requested too often.
+fn scan_error(error: zbus::Error) -> Error {
+    match &error {
+        zbus::Error::MethodError(name, Some(message), _)
+            if name.as_str() == "org.freedesktop.NetworkManager.Device.NotAllowed"
+                && message.contains("previous scan") =>
+        {
+            Error::ScanRateLimited
+        }
+        _ => Error::ZBus(error),
+    }
+}
+
+/// Choose the security settings to connect to an access point with.
+fn security_for(
+    security: AccessPointSecurity,
+    password: Option<&str>,
+) -> Result<Option<WirelessSecuritySetting>, Error> {
+    if security.is_enterprise() {
+        return Err(Error::InvalidSetting(format!(
+            "{} is required to connect to enterprise networks",
+            Ieee8021xSetting::NAME
+        )));
+    }
+
+    let Some(key_mgmt) = security.key_management() else {
+        return Ok(None);
+    };
+    let password = match password {
+        Some(password) => password,
+        None if security.needs_password() => {
+            return Err(Error::InvalidSetting(format!(
+                "{}.psk is required to connect to this network",
+                WirelessSecuritySetting::NAME
+            )))
+        }
+        None => "",
+    };
+
+    match key_mgmt {
+        KeyManagement::None => Ok(Some(WirelessSecuritySetting {
+            key_mgmt: Some(KeyManagement::None),
+            wep_key0: Some(password.into()),
+            wep_key_type: Some(1),
+            ..Default::default()
+        })),
+        KeyManagement::WpaPsk => WirelessSecuritySetting::wpa_psk(password).map(Some),
+        KeyManagement::Sae => WirelessSecuritySetting::sae(password).map(Some),
+        _ => Ok(Some(WirelessSecuritySetting {
+            key_mgmt: Some(key_mgmt),
+            ..Default::default()
+        })),
+    }
+}
+
+/// Put the key management and key of `security` in the security setting of a saved profile.
+///
+/// The other properties of the profile, like PMF or the allowed protocols and ciphers, are kept.
+fn update_security(
+    existing: &mut Option<WirelessSecuritySetting>,
+    security: Option<WirelessSecuritySetting>,
+) {
+    match (existing.as_mut(), security) {
+        (Some(existing), Some(security)) => {
+            existing.key_mgmt = security.key_mgmt;
+            if security.psk.is_some() {
+                existing.psk = security.psk;
+            }
+            if security.wep_key0.is_some() {
+                existing.wep_key0 = security.wep_key0;
+                existing.wep_key_type = security.wep_key_type;
+            }
+        }
+        (_, security) => *existing = security,
+    }
+}
+
 impl WirelessDevice {
     /// The permanent hardware address of the device.
     pub async fn permanent_hardware_address(&self) -> Result<String, Error> {
@@ -131,3 +497,57 @@ impl WirelessDevice {
         }
     }
 }
+
+/// An access point appearing or disappearing.
+///
+/// Obtained from [`WirelessDevice::access_point_events()`].
+#[derive(Clone, Debug)]
+pub enum AccessPointEvent {
+    /// An access point became visible to the device.
+    Added(AccessPoint),
+
+    /// An access point is no longer visible to the device.
+    ///
+    /// The access point no longer exists, so only its object path is available.
+    Removed(OwnedObjectPath),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setting::Pmf;
+
+    #[test]
+    fn update_security_keeps_other_properties() {
+        let mut existing = Some(WirelessSecuritySetting {
+            pmf: Some(Pmf::Required),
+            proto: Some(vec!["rsn".into()]),
+            group: Some(vec!["ccmp".into()]),
+            ..WirelessSecuritySetting::wpa_psk("old password").unwrap()
+        });
+        update_security(
+            &mut existing,
+            Some(WirelessSecuritySetting::sae("new password").unwrap()),
+        );
+        let existing = existing.unwrap();
+        assert_eq!(existing.key_mgmt, Some(KeyManagement::Sae));
+        assert_eq!(existing.psk.as_deref(), Some("new password"));
+        assert_eq!(existing.pmf, Some(Pmf::Required));
+        assert_eq!(existing.proto, Some(vec!["rsn".to_owned()]));
+        assert_eq!(existing.group, Some(vec!["ccmp".to_owned()]));
+    }
+
+    #[test]
+    fn update_security_of_open_and_new_profiles() {
+        let wep = security_for(AccessPointSecurity::Wep, Some("0123456789")).unwrap();
+        let mut existing = None;
+        update_security(&mut existing, wep);
+        let existing_wep = existing.as_ref().unwrap();
+        assert_eq!(existing_wep.key_mgmt, Some(KeyManagement::None));
+        assert_eq!(existing_wep.wep_key0.as_deref(), Some("0123456789"));
+        assert_eq!(existing_wep.wep_key_type, Some(1));
+
+        update_security(&mut existing, None);
+        assert_eq!(existing, None);
+    }
+}
@@ -1,7 +1,9 @@
+use futures_lite::Stream;
 use num_traits::FromPrimitive;
 use zbus::{zvariant::OwnedObjectPath, Connection};
 
 use crate::{
+    setting::KeyManagement,
     types::{AccessPointCapabilityFlags, AccessPointMode, AccessPointSecurityFlags},
     Error,
 };
@@ -17,6 +19,32 @@ pub struct AccessPoint {
 
 crate::zproxy_pathed!(AccessPoint, crate::raw::accesspoint::AccessPointProxy<'_>);
 
+impl AccessPoint {
+    /// Subscribe to changes of the signal quality of the access point.
+    ///
+    /// The stream yields the new signal quality, in percent, each time it changes. Changes that
+    /// occurred before this method was called are not reported.
+    pub async fn strength_changes(
+        &self,
+    ) -> Result<impl Stream<Item = Result<u8, Error>> + Unpin, Error> {
+        crate::util::property_changes(self.raw().await?.inner(), "Strength").await
+    }
+}
+
+impl AccessPoint {
+    /// The kind of security the access point uses.
+    ///
+    /// This is derived from the [capability](Self::capability_flags()),
+    /// [WPA](Self::wpa_security_flags()) and [RSN](Self::rsn_security_flags()) flags.
+    pub async fn security(&self) -> Result<AccessPointSecurity, Error> {
+        Ok(AccessPointSecurity::from_flags(
+            self.capability_flags().await?,
+            self.wpa_security_flags().await?,
+            self.rsn_security_flags().await?,
+        ))
+    }
+}
+
 impl AccessPoint {
     /// Flags describing the capabilities of the access point.
     pub async fn capability_flags(&self) -> Result<AccessPointCapabilityFlags, Error> {
@@ -51,6 +79,22 @@ impl AccessPoint {
         self.raw().await?.frequency().await.map_err(Error::ZBus)
     }
 
+    /// The channel number the access point is on, within its [band](Self::band()).
+    ///
+    /// A value of None means the frequency is not a known Wi-Fi channel.
+    pub async fn channel(&self) -> Result<Option<u32>, Error> {
+        Ok(super::channel_from_frequency(self.frequency().await?))
+    }
+
+    /// The frequency band the access point is on.
+    ///
+    /// A value of None means the frequency is not a known Wi-Fi channel.
+    pub async fn band(&self) -> Result<Option<super::FrequencyBand>, Error> {
+        Ok(super::FrequencyBand::from_frequency(
+            self.frequency().await?,
+        ))
+    }
+
     /// The hardware address (BSSID) of the access point.
     pub async fn bssid(&self) -> Result<String, Error> {
         self.raw().await?.hw_address().await.map_err(Error::ZBus)
@@ -86,3 +130,209 @@ impl AccessPoint {
         }
     }
 }
+
+/// The kind of security an access point uses.
+///
+/// Obtained from [`AccessPoint::security()`]. Access points often support several kinds of
+/// security at once; this is the most secure kind that a client can use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessPointSecurity {
+    /// No security.
+    Open,
+
+    /// Enhanced Open (Opportunistic Wireless Encryption).
+    ///
+    /// The traffic is encrypted, but there is no authentication.
+    Owe,
+
+    /// An open network that also offers Enhanced Open on a companion access point.
+    OweTransition,
+
+    /// Static WEP. This is insecure.
+    Wep,
+
+    /// WPA Personal, with a pre-shared key.
+    WpaPsk,
+
+    /// WPA2 Personal, with a pre-shared key.
+    ///
+    /// The access point may also accept WPA Personal.
+    Wpa2Psk,
+
+    /// WPA2/WPA3 Personal transition mode, where the access point accepts both.
+    Wpa2Wpa3Psk,
+
+    /// WPA3 Personal (Simultaneous Authentication of Equals).
+    Wpa3Sae,
+
+    /// WPA/WPA2/WPA3 Enterprise, with 802.1X authentication.
+    Enterprise,
+
+    /// WPA3 Enterprise in 192-bit mode (Suite-B).
+    Wpa3SuiteB192,
+}
+
+impl AccessPointSecurity {
+    /// Classify the security of an access point from its flags.
+    pub fn from_flags(
+        capabilities: AccessPointCapabilityFlags,
+        wpa: AccessPointSecurityFlags,
+        rsn: AccessPointSecurityFlags,
+    ) -> Self {
+        use AccessPointSecurityFlags as F;
+
+        if rsn.contains(F::KEY_MGMT_EAP_SUITE_B_192) {
+            Self::Wpa3SuiteB192
+        } else if (wpa | rsn).contains(F::KEY_MGMT_802_1X) {
+            Self::Enterprise
+        } else if rsn.contains(F::KEY_MGMT_SAE) {
+            if rsn.contains(F::KEY_MGMT_PSK) {
+                Self::Wpa2Wpa3Psk
+            } else {
+                Self::Wpa3Sae
+            }
+        } else if rsn.contains(F::KEY_MGMT_PSK) {
+            Self::Wpa2Psk
+        } else if wpa.contains(F::KEY_MGMT_PSK) {
+            Self::WpaPsk
+        } else if rsn.contains(F::KEY_MGMT_OWE) {
+            Self::Owe
+        } else if rsn.contains(F::KEY_MGMT_OWE_TM) {
+            Self::OweTransition
+        } else if capabilities.contains(AccessPointCapabilityFlags::PRIVACY) {
+            Self::Wep
+        } else {
+            Self::Open
+        }
+    }
+
+    /// The key management to use in the
+    /// [`WirelessSecuritySetting`](crate::setting::WirelessSecuritySetting) of a profile for this
+    /// access point.
+    ///
+    /// This is None for open networks, which have no security settings. Note that WEP uses
+    /// [`KeyManagement::None`]. In WPA2/WPA3 transition mode, this is
+    /// [`KeyManagement::WpaPsk`], which works with more devices.
+    pub fn key_management(&self) -> Option<KeyManagement> {
+        match self {
+            Self::Open => None,
+            Self::Owe | Self::OweTransition => Some(KeyManagement::Owe),
+            Self::Wep => Some(KeyManagement::None),
+            Self::WpaPsk | Self::Wpa2Psk | Self::Wpa2Wpa3Psk => Some(KeyManagement::WpaPsk),
+            Self::Wpa3Sae => Some(KeyManagement::Sae),
+            Self::Enterprise => Some(KeyManagement::WpaEap),
+            Self::Wpa3SuiteB192 => Some(KeyManagement::WpaEapSuiteB192),
+        }
+    }
+
+    /// Whether connecting needs a password or key.
+    ///
+    /// This is false for enterprise networks, which need 802.1X credentials instead.
+    pub fn needs_password(&self) -> bool {
+        matches!(
+            self,
+            Self::Wep | Self::WpaPsk | Self::Wpa2Psk | Self::Wpa2Wpa3Psk | Self::Wpa3Sae
+        )
+    }
+
+    /// Whether the network uses 802.1X authentication.
+    pub fn is_enterprise(&self) -> bool {
+        matches!(self, Self::Enterprise | Self::Wpa3SuiteB192)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use AccessPointSecurityFlags as F;
+
+    const PRIVACY: AccessPointCapabilityFlags = AccessPointCapabilityFlags::PRIVACY;
+    const NONE: AccessPointSecurityFlags = AccessPointSecurityFlags::empty();
+    const CCMP: AccessPointSecurityFlags = F::PAIR_CCMP.union(F::GROUP_CCMP);
+
+    fn classify(
+        capabilities: AccessPointCapabilityFlags,
+        wpa: AccessPointSecurityFlags,
+        rsn: AccessPointSecurityFlags,
+    ) -> AccessPointSecurity {
+        AccessPointSecurity::from_flags(capabilities, wpa, rsn)
+    }
+
+    #[test]
+    fn open() {
+        let security = classify(AccessPointCapabilityFlags::empty(), NONE, NONE);
+        assert_eq!(security, AccessPointSecurity::Open);
+        assert_eq!(security.key_management(), None);
+        assert!(!security.needs_password());
+    }
+
+    #[test]
+    fn wep() {
+        let security = classify(PRIVACY, NONE, NONE);
+        assert_eq!(security, AccessPointSecurity::Wep);
+        assert_eq!(security.key_management(), Some(KeyManagement::None));
+        assert!(security.needs_password());
+    }
+
+    #[test]
+    fn wpa_psk() {
+        let tkip = F::PAIR_TKIP | F::GROUP_TKIP | F::KEY_MGMT_PSK;
+        assert_eq!(classify(PRIVACY, tkip, NONE), AccessPointSecurity::WpaPsk);
+
+        let rsn = CCMP | F::KEY_MGMT_PSK;
+        assert_eq!(classify(PRIVACY, NONE, rsn), AccessPointSecurity::Wpa2Psk);
+        assert_eq!(classify(PRIVACY, tkip, rsn), AccessPointSecurity::Wpa2Psk);
+        assert_eq!(
+            AccessPointSecurity::Wpa2Psk.key_management(),
+            Some(KeyManagement::WpaPsk)
+        );
+    }
+
+    #[test]
+    fn sae() {
+        let security = classify(PRIVACY, NONE, CCMP | F::KEY_MGMT_SAE);
+        assert_eq!(security, AccessPointSecurity::Wpa3Sae);
+        assert_eq!(security.key_management(), Some(KeyManagement::Sae));
+
+        let security = classify(PRIVACY, NONE, CCMP | F::KEY_MGMT_SAE | F::KEY_MGMT_PSK);
+        assert_eq!(security, AccessPointSecurity::Wpa2Wpa3Psk);
+        assert_eq!(security.key_management(), Some(KeyManagement::WpaPsk));
+    }
+
+    #[test]
+    fn owe() {
+        let security = classify(PRIVACY, NONE, CCMP | F::KEY_MGMT_OWE);
+        assert_eq!(security, AccessPointSecurity::Owe);
+        assert!(!security.needs_password());
+
+        let security = classify(
+            AccessPointCapabilityFlags::empty(),
+            NONE,
+            F::KEY_MGMT_OWE_TM,
+        );
+        assert_eq!(security, AccessPointSecurity::OweTransition);
+        assert_eq!(security.key_management(), Some(KeyManagement::Owe));
+    }
+
+    #[test]
+    fn enterprise() {
+        let security = classify(PRIVACY, NONE, CCMP | F::KEY_MGMT_802_1X);
+        assert_eq!(security, AccessPointSecurity::Enterprise);
+        assert!(security.is_enterprise());
+        assert!(!security.needs_password());
+
+        let wpa = F::PAIR_TKIP | F::GROUP_TKIP | F::KEY_MGMT_802_1X;
+        assert_eq!(
+            classify(PRIVACY, wpa, NONE),
+            AccessPointSecurity::Enterprise
+        );
+
+        let security = classify(PRIVACY, NONE, CCMP | F::KEY_MGMT_EAP_SUITE_B_192);
+        assert_eq!(security, AccessPointSecurity::Wpa3SuiteB192);
+        assert_eq!(
+            security.key_management(),
+            Some(KeyManagement::WpaEapSuiteB192)
+        );
+    }
+}
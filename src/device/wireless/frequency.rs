@@ -0,0 +1,174 @@
+use crate::setting::WirelessBand;
+
+/// A Wi-Fi frequency band.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FrequencyBand {
+    /// The 2.4 GHz band, channels 1 to 14.
+    Ghz2_4,
+
+    /// The 5 GHz band, including the 4.9 GHz channels used in Japan.
+    Ghz5,
+
+    /// The 6 GHz band (UNII-5 to UNII-8), used by Wi-Fi 6E and later.
+    Ghz6,
+
+    /// The 60 GHz band (802.11ad/ay), channels 1 to 6.
+    Ghz60,
+}
+
+impl FrequencyBand {
+    /// The band a frequency in MHz is in.
+    ///
+    /// Returns None if the frequency is not a Wi-Fi channel.
+    pub fn from_frequency(frequency: u32) -> Option<Self> {
+        decode(frequency).map(|(band, _)| band)
+    }
+
+    /// The centre frequency in MHz of a channel in this band.
+    ///
+    /// This is the reverse of [`channel_from_frequency()`]. Returns None if the band has no such
+    /// channel.
+    pub fn frequency(&self, channel: u32) -> Option<u32> {
+        match (self, channel, channel % 4) {
+            (FrequencyBand::Ghz2_4, 1..=13, _) => Some(2407 + 5 * channel),
+            (FrequencyBand::Ghz2_4, 14, _) => Some(2484),
+            (FrequencyBand::Ghz5, 184..=196, 0) => Some(4000 + 5 * channel),
+            (FrequencyBand::Ghz5, 32..=144, 0) => Some(5000 + 5 * channel),
+            (FrequencyBand::Ghz5, 149..=177, 1) => Some(5000 + 5 * channel),
+            (FrequencyBand::Ghz6, 2, _) => Some(5935),
+            (FrequencyBand::Ghz6, 1..=233, 1) => Some(5950 + 5 * channel),
+            (FrequencyBand::Ghz60, 1..=6, _) => Some(58320 + 2160 * (channel - 1)),
+            _ => None,
+        }
+    }
+
+    /// The `band` to use in a [`WirelessSetting`](crate::setting::WirelessSetting) for this band.
+    ///
+    /// Connection profiles can only be restricted to the 2.4 and 5 GHz bands, so this is None for
+    /// the others.
+    pub fn setting_band(&self) -> Option<WirelessBand> {
+        match self {
+            FrequencyBand::Ghz2_4 => Some(WirelessBand::Bg),
+            FrequencyBand::Ghz5 => Some(WirelessBand::A),
+            FrequencyBand::Ghz6 | FrequencyBand::Ghz60 => None,
+        }
+    }
+}
+
+/// The IEEE 802.11 channel number of a frequency in MHz.
+///
+/// This follows the same numbering as `nmcli`. Channel numbers are only unique within a band: see
+/// [`FrequencyBand::from_frequency()`]. Returns None if the frequency is not a Wi-Fi channel.
+pub fn channel_from_frequency(frequency: u32) -> Option<u32> {
+    decode(frequency).map(|(_, channel)| channel)
+}
+
+/// Whether a frequency in MHz requires Dynamic Frequency Selection.
+///
+/// In most regulatory domains, the 5 GHz channels 52 to 144 are shared with radars. Access points
+/// must check for radars before using them and vacate them when one is detected, so networks on
+/// these channels may take longer to come up and can move to another channel.
+pub fn is_dfs_frequency(frequency: u32) -> bool {
+    (5250..=5730).contains(&frequency)
+}
+
+fn decode(frequency: u32) -> Option<(FrequencyBand, u32)> {
+    let (band, channel) = match frequency {
+        2484 => (FrequencyBand::Ghz2_4, 14),
+        2412..=2472 => (FrequencyBand::Ghz2_4, (frequency - 2407) / 5),
+        4920..=4980 => (FrequencyBand::Ghz5, (frequency - 4000) / 5),
+        5160..=5885 => (FrequencyBand::Ghz5, (frequency - 5000) / 5),
+        5935 => (FrequencyBand::Ghz6, 2),
+        5955..=7115 => (FrequencyBand::Ghz6, (frequency - 5950) / 5),
+        58320..=69120 => (FrequencyBand::Ghz60, (frequency - 58320) / 2160 + 1),
+        _ => return None,
+    };
+
+    // reject frequencies between channels
+    (band.frequency(channel) == Some(frequency)).then_some((band, channel))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANNELS: &[(FrequencyBand, u32, u32)] = &[
+        (FrequencyBand::Ghz2_4, 1, 2412),
+        (FrequencyBand::Ghz2_4, 6, 2437),
+        (FrequencyBand::Ghz2_4, 13, 2472),
+        (FrequencyBand::Ghz2_4, 14, 2484),
+        (FrequencyBand::Ghz5, 184, 4920),
+        (FrequencyBand::Ghz5, 196, 4980),
+        (FrequencyBand::Ghz5, 32, 5160),
+        (FrequencyBand::Ghz5, 36, 5180),
+        (FrequencyBand::Ghz5, 64, 5320),
+        (FrequencyBand::Ghz5, 100, 5500),
+        (FrequencyBand::Ghz5, 144, 5720),
+        (FrequencyBand::Ghz5, 149, 5745),
+        (FrequencyBand::Ghz5, 165, 5825),
+        (FrequencyBand::Ghz5, 177, 5885),
+        (FrequencyBand::Ghz6, 1, 5955),
+        (FrequencyBand::Ghz6, 2, 5935),
+        (FrequencyBand::Ghz6, 5, 5975),
+        (FrequencyBand::Ghz6, 233, 7115),
+        (FrequencyBand::Ghz60, 1, 58320),
+        (FrequencyBand::Ghz60, 2, 60480),
+        (FrequencyBand::Ghz60, 6, 69120),
+    ];
+
+    #[test]
+    fn channels() {
+        for &(band, channel, frequency) in CHANNELS {
+            assert_eq!(
+                FrequencyBand::from_frequency(frequency),
+                Some(band),
+                "{frequency}"
+            );
+            assert_eq!(
+                channel_from_frequency(frequency),
+                Some(channel),
+                "{frequency}"
+            );
+            assert_eq!(
+                band.frequency(channel),
+                Some(frequency),
+                "{band:?} {channel}"
+            );
+        }
+    }
+
+    #[test]
+    fn not_channels() {
+        for frequency in [
+            0, 2407, 2418, 2477, 2482, 5150, 5185, 5175, 5730, 5960, 7120,
+        ] {
+            assert_eq!(channel_from_frequency(frequency), None, "{frequency}");
+            assert_eq!(
+                FrequencyBand::from_frequency(frequency),
+                None,
+                "{frequency}"
+            );
+        }
+
+        for (band, channel) in [
+            (FrequencyBand::Ghz2_4, 0),
+            (FrequencyBand::Ghz2_4, 15),
+            (FrequencyBand::Ghz5, 37),
+            (FrequencyBand::Ghz5, 148),
+            (FrequencyBand::Ghz5, 183),
+            (FrequencyBand::Ghz6, 3),
+            (FrequencyBand::Ghz6, 237),
+            (FrequencyBand::Ghz60, 7),
+        ] {
+            assert_eq!(band.frequency(channel), None, "{band:?} {channel}");
+        }
+    }
+
+    #[test]
+    fn dfs() {
+        assert!(!is_dfs_frequency(5240));
+        assert!(is_dfs_frequency(5260));
+        assert!(is_dfs_frequency(5720));
+        assert!(!is_dfs_frequency(5745));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    active_connection::ActiveConnection, connection::Connection, device::Device, Error,
+    NetworkManager,
+};
+
+/// A Wi-Fi hotspot running on a device.
+///
+/// This is obtained from [`WirelessDevice::start_hotspot()`](super::WirelessDevice::start_hotspot()).
+/// Stopping the hotspot deactivates it, which deletes its profile, and reactivates the connection
+/// that was active on the device before the hotspot was started, if any.
+///
+/// If the handle is dropped without being stopped, the hotspot is stopped in the background, on
+/// the executor of the D-Bus connection. This cannot be awaited and its errors are ignored, so it
+/// is best effort: call [`Hotspot::stop()`] to know when and whether it worked. The hotspot is
+/// also bound to the D-Bus connection, so NetworkManager deactivates it if the process exits
+/// without stopping it, but then the previous connection is not reactivated.
+#[derive(Debug)]
+#[must_use = "dropping the handle stops the hotspot"]
+pub struct Hotspot {
+    active: ActiveConnection,
+    device: Device,
+    previous: Option<Connection>,
+    stopped: bool,
+}
+
+impl Hotspot {
+    pub(crate) fn new(
+        active: ActiveConnection,
+        device: Device,
+        previous: Option<Connection>,
+    ) -> Self {
+        Self {
+            active,
+            device,
+            previous,
+            stopped: false,
+        }
+    }
+
+    /// Get the active connection of the hotspot.
+    pub fn active_connection(&self) -> &ActiveConnection {
+        &self.active
+    }
+
+    /// Stop the hotspot and restore the previous connection.
+    ///
+    /// The previous connection is reactivated even if the hotspot could not be deactivated, for
+    /// example because it was already stopped. The first error is returned.
+    pub async fn stop(mut self) -> Result<(), Error> {
+        let result = teardown(&self.active, &self.device, self.previous.as_ref()).await;
+        self.stopped = true;
+        result
+    }
+}
+
+impl Drop for Hotspot {
+    fn drop(&mut self) {
+        if self.stopped {
+            return;
+        }
+
+        let active = self.active.clone();
+        let device = self.device.clone();
+        let previous = self.previous.clone();
+        self.device
+            .zbus
+            .executor()
+            .spawn(
+                async move {
+                    let _ = teardown(&active, &device, previous.as_ref()).await;
+                },
+                "hotspot teardown",
+            )
+            .detach();
+    }
+}
+
+async fn teardown(
+    active: &ActiveConnection,
+    device: &Device,
+    previous: Option<&Connection>,
+) -> Result<(), Error> {
+    let nm = NetworkManager::new_with_zbus(device.zbus.clone());
+    let deactivated = nm.deactivate_connection(active).await;
+    let reactivated = match previous {
+        Some(previous) => nm
+            .activate_connection(previous, Some(device), None)
+            .await
+            .map(drop),
+        None => Ok(()),
+    };
+    deactivated.and(reactivated)
+}
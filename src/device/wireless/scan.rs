@@ -0,0 +1,234 @@
+use std::cmp::Reverse;
+
+use num_traits::FromPrimitive;
+
+use crate::{
+    types::{AccessPointCapabilityFlags, AccessPointMode, AccessPointSecurityFlags},
+    Error,
+};
+
+use super::{channel_from_frequency, AccessPoint, AccessPointSecurity, FrequencyBand};
+
+/// The access points seen in a scan for one network.
+///
+/// This is obtained from [`WirelessDevice::scan()`](super::WirelessDevice::scan()) and
+/// [`WirelessDevice::scan_results()`](super::WirelessDevice::scan_results()).
+#[derive(Clone, Debug)]
+pub struct ScanResult {
+    /// The SSID of the network, as raw octets.
+    ///
+    /// This is empty for the access points of hidden networks, which are grouped together.
+    pub ssid: Vec<u8>,
+
+    /// The access points of the network, strongest first.
+    ///
+    /// This is never empty in scan results.
+    pub access_points: Vec<ScannedAccessPoint>,
+}
+
+impl ScanResult {
+    /// The SSID of the network as a string, with invalid UTF-8 replaced.
+    pub fn ssid_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.ssid).into_owned()
+    }
+
+    /// The access point with the strongest signal.
+    ///
+    /// Returns None if there are no access points, which only happens if they were removed.
+    pub fn strongest(&self) -> Option<&ScannedAccessPoint> {
+        self.access_points.first()
+    }
+
+    /// Group access points by SSID, into networks sorted by their strongest access point.
+    pub(crate) fn group(access_points: Vec<(Vec<u8>, ScannedAccessPoint)>) -> Vec<Self> {
+        group_by_ssid(access_points, |ap| ap.strength)
+            .into_iter()
+            .map(|(ssid, access_points)| Self {
+                ssid,
+                access_points,
+            })
+            .collect()
+    }
+}
+
+/// Group items by SSID, strongest first, with the groups sorted by their strongest item.
+///
+/// Groups are kept in the order they were first seen when their strongest items are as strong.
+fn group_by_ssid<T>(
+    items: Vec<(Vec<u8>, T)>,
+    strength: impl Fn(&T) -> u8,
+) -> Vec<(Vec<u8>, Vec<T>)> {
+    let mut groups: Vec<(Vec<u8>, Vec<T>)> = Vec::new();
+    for (ssid, item) in items {
+        match groups.iter_mut().find(|(group, _)| *group == ssid) {
+            Some((_, group)) => group.push(item),
+            None => groups.push((ssid, vec![item])),
+        }
+    }
+
+    for (_, group) in &mut groups {
+        group.sort_by_key(|item| Reverse(strength(item)));
+    }
+    groups.sort_by_key(|(_, group)| Reverse(group.first().map(&strength)));
+    groups
+}
+
+/// A snapshot of the properties of an access point, as seen in a scan.
+#[derive(Clone, Debug)]
+pub struct ScannedAccessPoint {
+    /// The access point, to query it for up to date information.
+    pub access_point: AccessPoint,
+
+    /// The hardware address (BSSID) of the access point.
+    pub bssid: String,
+
+    /// The radio channel frequency in use by the access point, in MHz.
+    pub frequency: u32,
+
+    /// The frequency band the access point is on, if the frequency is a known Wi-Fi channel.
+    pub band: Option<FrequencyBand>,
+
+    /// The channel number the access point is on, if the frequency is a known Wi-Fi channel.
+    pub channel: Option<u32>,
+
+    /// The signal quality of the access point, in percent.
+    pub strength: u8,
+
+    /// The maximum bitrate this access point is capable of, in kilobits/second (Kb/s).
+    pub max_bitrate: u32,
+
+    /// The operating mode of the access point.
+    pub mode: AccessPointMode,
+
+    /// The kind of security the access point uses, derived from its flags.
+    pub security: AccessPointSecurity,
+
+    /// Flags describing the capabilities of the access point.
+    pub capability_flags: AccessPointCapabilityFlags,
+
+    /// Flags describing the access point's capabilities according to WPA.
+    pub wpa_security_flags: AccessPointSecurityFlags,
+
+    /// Flags describing the access point's capabilities according to RSN.
+    pub rsn_security_flags: AccessPointSecurityFlags,
+
+    /// The timestamp for the last time the access point was found in scan results, in
+    /// `CLOCK_BOOTTIME` seconds.
+    pub last_seen: Option<u32>,
+}
+
+impl ScannedAccessPoint {
+    /// Read the properties of an access point, with its SSID.
+    ///
+    /// Returns None if the access point disappeared before its properties could be read, which is
+    /// common right after a scan.
+    pub(crate) async fn new(access_point: AccessPoint) -> Result<Option<(Vec<u8>, Self)>, Error> {
+        match Self::read(access_point).await {
+            Err(Error::ZBus(error)) if is_unknown_object(&error) => Ok(None),
+            result => result.map(Some),
+        }
+    }
+
+    async fn read(access_point: AccessPoint) -> Result<(Vec<u8>, Self), Error> {
+        let raw = access_point.raw().await?;
+        let frequency = raw.frequency().await?;
+        let capability_flags = AccessPointCapabilityFlags::from_bits_retain(raw.flags().await?);
+        let wpa_security_flags = AccessPointSecurityFlags::from_bits_retain(raw.wpa_flags().await?);
+        let rsn_security_flags = AccessPointSecurityFlags::from_bits_retain(raw.rsn_flags().await?);
+        Ok((
+            raw.ssid().await?,
+            Self {
+                bssid: raw.hw_address().await?,
+                frequency,
+                band: FrequencyBand::from_frequency(frequency),
+                channel: channel_from_frequency(frequency),
+                strength: raw.strength().await?,
+                max_bitrate: raw.max_bitrate().await?,
+                mode: FromPrimitive::from_u32(raw.mode().await?).ok_or(Error::UnsupportedType)?,
+                security: AccessPointSecurity::from_flags(
+                    capability_flags,
+                    wpa_security_flags,
+                    rsn_security_flags,
+                ),
+                capability_flags,
+                wpa_security_flags,
+                rsn_security_flags,
+                last_seen: u32::try_from(raw.last_seen().await?).ok(),
+                access_point,
+            },
+        ))
+    }
+}
+
+/// Whether an error is because the object no longer exists.
+///
+/// NetworkManager reports calls to removed objects as unknown methods, as GDBus does.
+fn is_unknown_object(error: &zbus::Error) -> bool {
+    use zbus::fdo::Error as Fdo;
+
+    match error {
+        zbus::Error::MethodError(name, _, _) => [
+            "org.freedesktop.DBus.Error.UnknownObject",
+            "org.freedesktop.DBus.Error.UnknownMethod",
+        ]
+        .contains(&name.as_str()),
+        zbus::Error::FDO(error) => matches!(**error, Fdo::UnknownObject(_) | Fdo::UnknownMethod(_)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ssids(groups: &[(Vec<u8>, Vec<u8>)]) -> Vec<&[u8]> {
+        groups.iter().map(|(ssid, _)| ssid.as_slice()).collect()
+    }
+
+    #[test]
+    fn groups_by_ssid() {
+        let groups = group_by_ssid(
+            vec![
+                (b"Home".to_vec(), 40),
+                (b"Cafe".to_vec(), 60),
+                (Vec::new(), 20),
+                (b"Home".to_vec(), 70),
+                (Vec::new(), 30),
+            ],
+            |strength| *strength,
+        );
+        assert_eq!(ssids(&groups), [&b"Home"[..], b"Cafe", b""]);
+        assert_eq!(groups[0].1, [70, 40]);
+        assert_eq!(groups[1].1, [60]);
+        assert_eq!(groups[2].1, [30, 20]);
+    }
+
+    #[test]
+    fn orders_by_strength() {
+        let groups = group_by_ssid(
+            vec![
+                (b"Weak".to_vec(), 10),
+                (b"Strong".to_vec(), 90),
+                (b"Tie".to_vec(), 50),
+                (b"Other tie".to_vec(), 50),
+                (b"Weak".to_vec(), 15),
+            ],
+            |strength| *strength,
+        );
+        assert_eq!(
+            ssids(&groups),
+            [&b"Strong"[..], b"Tie", b"Other tie", b"Weak"]
+        );
+        assert_eq!(groups[3].1, [15, 10]);
+        assert!(group_by_ssid(Vec::<(Vec<u8>, u8)>::new(), |strength| *strength).is_empty());
+    }
+
+    #[test]
+    fn strongest_of_no_access_points() {
+        let network = ScanResult {
+            ssid: b"Home".to_vec(),
+            access_points: Vec::new(),
+        };
+        assert!(network.strongest().is_none());
+    }
+}
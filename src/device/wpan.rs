@@ -0,0 +1,18 @@
+use super::Device;
+
+/// An IEEE 802.15.4 (WPAN) device.
+#[derive(Clone, Debug)]
+pub struct WpanDevice {
+    pub(crate) parent: Device,
+}
+
+impl WpanDevice {
+    pub(crate) fn new(parent: Device) -> Self {
+        Self { parent }
+    }
+
+    /// Get the underlying [`Device`].
+    pub fn device(&self) -> &Device {
+        &self.parent
+    }
+}
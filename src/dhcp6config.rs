@@ -0,0 +1,19 @@
+use crate::errors::Error;
+use zbus::{zvariant::OwnedObjectPath, Connection};
+
+#[derive(Clone, Debug)]
+pub struct Dhcp6Config {
+    pub(crate) zbus: Connection,
+    pub(crate) path: OwnedObjectPath,
+}
+
+crate::zproxy_pathed!(Dhcp6Config, crate::raw::dhcp6config::DHCP6ConfigProxy<'_>);
+
+impl Dhcp6Config {
+    /// Configuration options returned by the DHCP server.
+    pub async fn options(
+        &self,
+    ) -> Result<std::collections::HashMap<String, zbus::zvariant::OwnedValue>, Error> {
+        self.raw().await?.options().await.map_err(Error::ZBus)
+    }
+}
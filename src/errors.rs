@@ -1,3 +1,5 @@
+use crate::types::ActiveConnectionStateReason;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("DBus error: {0}")]
@@ -10,4 +12,16 @@ pub enum Error {
     UnsupportedType,
     #[error("Missing destination")]
     MissingDestination,
+    #[error("Timed out")]
+    Timeout,
+    #[error("Invalid setting: {0}")]
+    InvalidSetting(String),
+    #[error("Activation failed: {0:?}")]
+    ActivationFailed(ActiveConnectionStateReason),
+    #[error("Access point not found")]
+    AccessPointNotFound,
+    #[error("Scan requested too soon after the previous one")]
+    ScanRateLimited,
+    #[error("Parse error at line {line}: {message}")]
+    Parse { line: usize, message: String },
 }
@@ -1,6 +1,7 @@
 use crate::errors::Error;
 use zbus::{zvariant::OwnedObjectPath, Connection};
 
+#[derive(Clone, Debug)]
 pub struct Ip4Config {
     pub(crate) zbus: Connection,
     pub(crate) path: OwnedObjectPath,
@@ -0,0 +1,56 @@
+use crate::errors::Error;
+use zbus::{zvariant::OwnedObjectPath, Connection};
+
+#[derive(Clone, Debug)]
+pub struct Ip6Config {
+    pub(crate) zbus: Connection,
+    pub(crate) path: OwnedObjectPath,
+}
+
+crate::zproxy_pathed!(Ip6Config, crate::raw::ip6config::IP6ConfigProxy<'_>);
+
+impl Ip6Config {
+    pub async fn address_data(
+        &self,
+    ) -> Result<Vec<std::collections::HashMap<String, zbus::zvariant::OwnedValue>>, Error> {
+        self.raw().await?.address_data().await.map_err(Error::ZBus)
+    }
+
+    pub async fn addresses(&self) -> Result<Vec<(Vec<u8>, u32, Vec<u8>)>, Error> {
+        self.raw().await?.addresses().await.map_err(Error::ZBus)
+    }
+
+    pub async fn dns_options(&self) -> Result<Vec<String>, Error> {
+        self.raw().await?.dns_options().await.map_err(Error::ZBus)
+    }
+
+    pub async fn dns_priority(&self) -> Result<i32, Error> {
+        self.raw().await?.dns_priority().await.map_err(Error::ZBus)
+    }
+
+    pub async fn domains(&self) -> Result<Vec<String>, Error> {
+        self.raw().await?.domains().await.map_err(Error::ZBus)
+    }
+
+    pub async fn gateway(&self) -> Result<String, Error> {
+        self.raw().await?.gateway().await.map_err(Error::ZBus)
+    }
+
+    pub async fn nameservers(&self) -> Result<Vec<Vec<u8>>, Error> {
+        self.raw().await?.nameservers().await.map_err(Error::ZBus)
+    }
+
+    pub async fn route_data(
+        &self,
+    ) -> Result<Vec<std::collections::HashMap<String, zbus::zvariant::OwnedValue>>, Error> {
+        self.raw().await?.route_data().await.map_err(Error::ZBus)
+    }
+
+    pub async fn routes(&self) -> Result<Vec<(Vec<u8>, u32, Vec<u8>, u32)>, Error> {
+        self.raw().await?.routes().await.map_err(Error::ZBus)
+    }
+
+    pub async fn searches(&self) -> Result<Vec<String>, Error> {
+        self.raw().await?.searches().await.map_err(Error::ZBus)
+    }
+}
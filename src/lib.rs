@@ -40,21 +40,31 @@
 
 #![deny(rust_2018_idioms)]
 
+mod dhcp4config;
+mod dhcp6config;
 mod errors;
 mod ip4config;
+mod ip6config;
 mod networkmanager;
 mod raw;
 mod settings;
+mod util;
 
 // pub mod configs;
 // pub mod devices;
+pub mod active_connection;
+pub mod checkpoint;
 pub mod connection;
 pub mod device;
+pub mod setting;
 pub mod types;
 
+pub use crate::dhcp4config::Dhcp4Config;
+pub use crate::dhcp6config::Dhcp6Config;
 pub use crate::errors::Error;
 pub use crate::ip4config::Ip4Config;
-pub use crate::networkmanager::NetworkManager;
+pub use crate::ip6config::Ip6Config;
+pub use crate::networkmanager::{AddAndActivateOptions, NetworkManager, NetworkManagerEvent};
 pub use crate::settings::Settings;
 
 #[cfg(feature = "raw")]
@@ -1,10 +1,24 @@
-use zbus::Connection;
+use std::collections::HashMap;
 
+use futures_lite::{Stream, StreamExt};
+use num_traits::FromPrimitive;
+use zbus::{
+    zvariant::{ObjectPath, OwnedObjectPath, Value},
+    Connection,
+};
+
+use crate::active_connection::ActiveConnection;
+use crate::checkpoint::Checkpoint;
+use crate::connection::Connection as SettingsConnection;
 use crate::device::Device;
 use crate::errors::Error;
 use crate::raw::networkmanager::NetworkManagerProxy;
+use crate::setting::{borrow_dbus, ConnectionSettings};
 use crate::settings::Settings;
-use crate::types::ReloadFlags;
+use crate::types::{
+    BindActivation, CheckpointCreateFlags, ConnectivityState, NmState, PersistMode, ReloadFlags,
+};
+use crate::util::optional_path;
 
 #[derive(Clone, Debug)]
 pub struct NetworkManager {
@@ -79,20 +93,138 @@ impl NetworkManager {
         })
     }
 
-    // TODO: ActivateConnection()
-    // TODO: AddAndActivateConnection()
-    // TODO: AddAndActivateConnection2()
-    // TODO: DeactivateConnection()
+    /// Activate a connection using the supplied device.
+    ///
+    /// If `device` is `None`, NetworkManager picks the device from the connection profile. This is
+    /// only possible for connections that aren't bound to a hardware device (like VPNs), or that
+    /// name their interface in the profile.
+    ///
+    /// The `specific_object` is the path of a connection-type specific object to use, for example
+    /// an access point for Wi-Fi connections or the parent active connection for VPNs. If `None`,
+    /// NetworkManager chooses the most suitable object itself.
+    pub async fn activate_connection(
+        &self,
+        connection: &SettingsConnection,
+        device: Option<&Device>,
+        specific_object: Option<&ObjectPath<'_>>,
+    ) -> Result<ActiveConnection, Error> {
+        let root = ObjectPath::from_static_str_unchecked("/");
+        let path = self
+            .raw()
+            .await?
+            .activate_connection(
+                &connection.path,
+                device.map_or(&root, |dev| &dev.path),
+                specific_object.unwrap_or(&root),
+            )
+            .await?;
+        Ok(ActiveConnection {
+            zbus: self.zbus.clone(),
+            path,
+        })
+    }
+
+    /// Add a new connection profile and activate it in one step.
+    ///
+    /// The `settings` may be incomplete: NetworkManager fills in missing settings with defaults
+    /// appropriate for the `device` and `specific_object`. For example, for Wi-Fi it is enough to
+    /// pass empty settings along with the access point to connect to, and NetworkManager
+    /// derives the SSID and security settings from it.
+    ///
+    /// See [`NetworkManager::activate_connection()`] for the meaning of `device` and
+    /// `specific_object`, and [`AddAndActivateOptions`] for how the new profile is stored.
+    ///
+    /// The settings that are present are checked with [`ConnectionSettings::validate()`] first.
+    ///
+    /// Returns the newly created connection profile and its active connection.
+    pub async fn add_and_activate_connection(
+        &self,
+        settings: &ConnectionSettings,
+        device: Option<&Device>,
+        specific_object: Option<&ObjectPath<'_>>,
+        options: AddAndActivateOptions,
+    ) -> Result<(SettingsConnection, ActiveConnection), Error> {
+        settings.validate()?;
+        let root = ObjectPath::from_static_str_unchecked("/");
+        let raw = settings.to_dbus()?;
+        let (path, active_path, _) = self
+            .raw()
+            .await?
+            .add_and_activate_connection2(
+                borrow_dbus(&raw)?,
+                device.map_or(&root, |dev| &dev.path),
+                specific_object.unwrap_or(&root),
+                options.to_dbus(),
+            )
+            .await?;
+        Ok((
+            SettingsConnection {
+                zbus: self.zbus.clone(),
+                path,
+            },
+            ActiveConnection {
+                zbus: self.zbus.clone(),
+                path: active_path,
+            },
+        ))
+    }
+
+    /// Deactivate an active connection.
+    pub async fn deactivate_connection(&self, active: &ActiveConnection) -> Result<(), Error> {
+        self.raw()
+            .await?
+            .deactivate_connection(&active.path)
+            .await
+            .map_err(Error::ZBus)
+    }
+
     // TODO: Sleep()
     // TODO: GetPermissions()
     // TODO: SetLogging()
     // TODO: GetLogging()
-    // TODO: CheckConnectivity()
-    // TODO: State()
-    // TODO: CheckpointCreate()
-    // TODO: CheckpointDestroy()
-    // TODO: CheckpointRollback()
-    // TODO: CheckpointAdjustRollbackTimeout()
+
+    /// Re-check the network connectivity state.
+    ///
+    /// This forces a new connectivity check, and returns its result once done. See
+    /// [`NetworkManager::connectivity()`].
+    pub async fn check_connectivity(&self) -> Result<ConnectivityState, Error> {
+        let value = self.raw().await?.check_connectivity().await?;
+        FromPrimitive::from_u32(value).ok_or(Error::UnsupportedType)
+    }
+
+    /// The overall networking state as determined by the NetworkManager daemon.
+    ///
+    /// This is based on the state of all active connections and the global connectivity state.
+    pub async fn state(&self) -> Result<NmState, Error> {
+        let value = self.raw().await?.state_property().await?;
+        FromPrimitive::from_u32(value).ok_or(Error::UnsupportedType)
+    }
+
+    /// Create a checkpoint of the current networking configuration for the given devices.
+    ///
+    /// If `devices` is empty, the checkpoint covers all devices.
+    ///
+    /// If `rollback_timeout` is not `None`, NetworkManager automatically rolls back to the
+    /// checkpoint after that many seconds, unless it is destroyed or rolled back before then.
+    ///
+    /// See [`Checkpoint`] and [`CheckpointGuard`](crate::checkpoint::CheckpointGuard).
+    pub async fn checkpoint_create(
+        &self,
+        devices: &[Device],
+        rollback_timeout: Option<u32>,
+        flags: CheckpointCreateFlags,
+    ) -> Result<Checkpoint, Error> {
+        let devices: Vec<ObjectPath<'_>> = devices.iter().map(|dev| dev.path.as_ref()).collect();
+        let path = self
+            .raw()
+            .await?
+            .checkpoint_create(&devices, rollback_timeout.unwrap_or(0), flags.bits())
+            .await?;
+        Ok(Checkpoint {
+            zbus: self.zbus.clone(),
+            path,
+        })
+    }
 
     pub async fn enable(&self, enabled: bool) -> Result<(), Error> {
         self.raw().await?.enable(enabled).await?;
@@ -131,6 +263,40 @@ impl NetworkManager {
         Ok(self.raw().await?.connectivity_check_enabled().await?)
     }
 
+    /// Enable or disable the connectivity checks.
+    ///
+    /// This only has an effect if connectivity checking is available, see
+    /// [`NetworkManager::is_connectivity_check_available()`]. It is not persistent, and is lost
+    /// when NetworkManager restarts.
+    pub async fn set_connectivity_check_enabled(&self, enabled: bool) -> Result<(), Error> {
+        self.raw()
+            .await?
+            .set_connectivity_check_enabled(enabled)
+            .await
+            .map_err(Error::ZBus)
+    }
+
+    /// Indicates whether connectivity checking is available.
+    ///
+    /// This depends on whether a connectivity check URI is configured in `NetworkManager.conf`.
+    pub async fn is_connectivity_check_available(&self) -> Result<bool, Error> {
+        Ok(self.raw().await?.connectivity_check_available().await?)
+    }
+
+    /// The URI that NetworkManager will hit to check if there is Internet connectivity.
+    pub async fn connectivity_check_uri(&self) -> Result<String, Error> {
+        Ok(self.raw().await?.connectivity_check_uri().await?)
+    }
+
+    /// The result of the last connectivity check.
+    ///
+    /// The connectivity check is only performed periodically, use
+    /// [`NetworkManager::check_connectivity()`] to force a re-check.
+    pub async fn connectivity(&self) -> Result<ConnectivityState, Error> {
+        let value = self.raw().await?.connectivity().await?;
+        FromPrimitive::from_u32(value).ok_or(Error::UnsupportedType)
+    }
+
     /// Indicates whether NetworkManager is still starting up.
     ///
     /// This becomes `false` when NetworkManager has finished attempting to activate every
@@ -139,8 +305,158 @@ impl NetworkManager {
         Ok(self.raw().await?.startup().await?)
     }
 
+    /// The list of active connections.
+    pub async fn active_connections(
+        &self,
+    ) -> Result<impl Iterator<Item = ActiveConnection> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .active_connections()
+            .await?
+            .into_iter()
+            .map(|path| ActiveConnection {
+                zbus: self.zbus.clone(),
+                path,
+            }))
+    }
+
+    /// The primary active connection.
+    ///
+    /// This is the connection being used to access the network, if any.
+    pub async fn primary_connection(&self) -> Result<Option<ActiveConnection>, Error> {
+        let path = self.raw().await?.primary_connection().await?;
+        Ok(optional_path(path).map(|path| ActiveConnection {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The connection type of the primary active connection, eg `802-3-ethernet`.
+    ///
+    /// A value of None means there is no primary connection.
+    pub async fn primary_connection_type(&self) -> Result<Option<String>, Error> {
+        let kind = self.raw().await?.primary_connection_type().await?;
+        Ok(if kind.is_empty() { None } else { Some(kind) })
+    }
+
+    /// The active connection that will likely become the primary connection once activated.
+    pub async fn activating_connection(&self) -> Result<Option<ActiveConnection>, Error> {
+        let path = self.raw().await?.activating_connection().await?;
+        Ok(optional_path(path).map(|path| ActiveConnection {
+            zbus: self.zbus.clone(),
+            path,
+        }))
+    }
+
+    /// The list of checkpoints that currently exist.
+    pub async fn checkpoints(&self) -> Result<impl Iterator<Item = Checkpoint> + '_, Error> {
+        Ok(self
+            .raw()
+            .await?
+            .checkpoints()
+            .await?
+            .into_iter()
+            .map(|path| Checkpoint {
+                zbus: self.zbus.clone(),
+                path,
+            }))
+    }
+
+    /// Subscribe to NetworkManager's global events.
+    ///
+    /// The stream yields an event whenever the overall networking state changes, a device is added
+    /// or removed, or the permissions of this client may have changed.
+    ///
+    /// Events that occurred before this method was called are not reported.
+    pub async fn events(
+        &self,
+    ) -> Result<impl Stream<Item = Result<NetworkManagerEvent, Error>> + Unpin, Error> {
+        let raw = self.raw().await?;
+
+        let state = raw.receive_state_changed().await?.map(|signal| {
+            let state = signal.args()?.state;
+            Ok(NetworkManagerEvent::StateChanged(
+                FromPrimitive::from_u32(state).ok_or(Error::UnsupportedType)?,
+            ))
+        });
+
+        let zbus = self.zbus.clone();
+        let added = raw.receive_device_added().await?.map(move |signal| {
+            Ok(NetworkManagerEvent::DeviceAdded(Device {
+                zbus: zbus.clone(),
+                path: signal.args()?.device_path.into(),
+            }))
+        });
+
+        let removed = raw.receive_device_removed().await?.map(|signal| {
+            Ok(NetworkManagerEvent::DeviceRemoved(
+                signal.args()?.device_path.into(),
+            ))
+        });
+
+        let permissions = raw
+            .receive_check_permissions()
+            .await?
+            .map(|_| Ok(NetworkManagerEvent::PermissionsChanged));
+
+        Ok(state.or(added).or(removed).or(permissions))
+    }
+
     /// Settings service object
     pub fn settings(&self) -> Settings {
         Settings::new_with_zbus(self.zbus.clone())
     }
 }
+
+/// Options for [`NetworkManager::add_and_activate_connection()`].
+///
+/// Options left to `None` use NetworkManager's defaults.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AddAndActivateOptions {
+    /// How the new connection profile is stored.
+    ///
+    /// Defaults to [`PersistMode::Disk`].
+    pub persist: Option<PersistMode>,
+
+    /// What the lifetime of the activation is bound to.
+    ///
+    /// Defaults to [`BindActivation::None`].
+    pub bind_activation: Option<BindActivation>,
+}
+
+impl AddAndActivateOptions {
+    fn to_dbus(self) -> HashMap<&'static str, Value<'static>> {
+        let mut options = HashMap::new();
+        if let Some(persist) = self.persist {
+            options.insert("persist", persist.as_str().into());
+        }
+        if let Some(bind) = self.bind_activation {
+            options.insert("bind-activation", bind.as_str().into());
+        }
+        options
+    }
+}
+
+/// A global event from NetworkManager.
+///
+/// Obtained from [`NetworkManager::events()`].
+#[derive(Clone, Debug)]
+pub enum NetworkManagerEvent {
+    /// The overall networking state changed.
+    StateChanged(NmState),
+
+    /// A device was added to the system.
+    DeviceAdded(Device),
+
+    /// A device was removed from the system.
+    ///
+    /// The device no longer exists, so only its object path is available.
+    DeviceRemoved(OwnedObjectPath),
+
+    /// The permissions of this client may have changed.
+    ///
+    /// This is emitted when system authorization details change, and the permissions should be
+    /// re-checked.
+    PermissionsChanged,
+}
@@ -0,0 +1,815 @@
+//! Typed connection settings.
+//!
+//! NetworkManager exchanges connection profiles over D-Bus as a map of _settings_ (like
+//! `connection`, `802-3-ethernet` or `ipv4`), each of which is a map of properties to values
+//! (`a{sa{sv}}`). [`ConnectionSettings`] models that structure with a typed struct per setting, so
+//! that properties have the type NetworkManager expects.
+//!
+//! The conversion is lossless: settings and properties that are not modelled by this crate are
+//! kept as-is in the `other` and `extra` fields, and are sent back unchanged. So are values this
+//! crate can't represent, like a key management it doesn't know: the property is then `None` and
+//! its value is kept in `extra`. This means it is safe to fetch a profile with
+//! [`Connection::settings()`](crate::connection::Connection::settings()), modify a few properties,
+//! and [`update`](crate::connection::Connection::update()) it.
+//!
+//! ```rust,no_run
+//! # use passcod_networkmanager as networkmanager;
+//! use networkmanager::{
+//!     setting::{ConnectionSetting, ConnectionSettings, EthernetSetting, Ipv4Method, Ipv4Setting},
+//!     Error, NetworkManager,
+//! };
+//!
+//! # async fn example() -> Result<(), Error> {
+//! let nm = NetworkManager::new().await?;
+//! let settings = ConnectionSettings {
+//!     connection: Some(ConnectionSetting {
+//!         id: Some("Wired".into()),
+//!         connection_type: Some("802-3-ethernet".into()),
+//!         interface_name: Some("eth0".into()),
+//!         ..Default::default()
+//!     }),
+//!     ethernet: Some(EthernetSetting::default()),
+//!     ipv4: Some(Ipv4Setting {
+//!         method: Some(Ipv4Method::Auto),
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! };
+//! nm.settings().add_connection(&settings).await?;
+//! # Ok(()) }
+//! ```
+
+use std::collections::HashMap;
+
+use zbus::zvariant::{OwnedValue, Signature, Type, Value};
+
+use crate::{types::SecretFlags, Error};
+
+#[doc(inline)]
+pub use bond::{BondMode, BondPortSetting, BondSetting, LacpRate, XmitHashPolicy};
+#[doc(inline)]
+pub use bridge::{BridgePortSetting, BridgeSetting};
+#[doc(inline)]
+pub use connection::{ConnectionSetting, MultiConnect};
+#[doc(inline)]
+pub use ethernet::EthernetSetting;
+#[doc(inline)]
+pub use ieee8021x::{Certificate, EapMethod, Ieee8021xSetting, Phase2Auth};
+#[doc(inline)]
+pub use ip::{IpAddress, IpRoute, RoutingRule};
+#[doc(inline)]
+pub use ip4::{Ipv4Method, Ipv4Setting};
+#[doc(inline)]
+pub use ip6::{Ipv6Method, Ipv6Setting};
+#[doc(inline)]
+pub use macvlan::{MacvlanMode, MacvlanSetting};
+#[doc(inline)]
+pub use vlan::{VlanFlags, VlanSetting};
+#[doc(inline)]
+pub use vxlan::VxlanSetting;
+#[doc(inline)]
+pub use wireguard::{WireGuardPeer, WireGuardPrivateKey, WireGuardPublicKey, WireGuardSetting};
+#[doc(inline)]
+pub use wireless::{MacAddressRandomization, WirelessBand, WirelessMode, WirelessSetting};
+#[doc(inline)]
+pub use wireless_security::{KeyManagement, Pmf, WirelessSecuritySetting};
+
+mod bond;
+mod bridge;
+mod connection;
+mod ethernet;
+mod ieee8021x;
+mod ip;
+mod ip4;
+mod ip6;
+mod keyfile;
+mod macvlan;
+mod vlan;
+mod vxlan;
+mod wg_quick;
+mod wireguard;
+mod wireless;
+mod wireless_security;
+
+/// Settings in the D-Bus form, as sent and received by NetworkManager.
+pub type RawSettings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// A single setting of a connection profile, like `connection` or `ipv4`.
+pub trait Setting: Sized {
+    /// The name of the setting, eg `802-3-ethernet`.
+    const NAME: &'static str;
+
+    /// Parse the setting from its D-Bus form.
+    fn from_dbus(properties: HashMap<String, OwnedValue>) -> Result<Self, Error>;
+
+    /// Convert the setting to its D-Bus form.
+    ///
+    /// Fails if a value kept as-is can't be copied, like a file descriptor that can't be duplicated.
+    fn to_dbus(&self) -> Result<HashMap<String, OwnedValue>, Error>;
+}
+
+/// The settings of a connection profile.
+///
+/// Settings that are absent are not sent to NetworkManager, which then uses its defaults.
+#[derive(Debug, Default, PartialEq)]
+pub struct ConnectionSettings {
+    /// General settings, common to all connection types.
+    pub connection: Option<ConnectionSetting>,
+
+    /// Wired Ethernet settings (`802-3-ethernet`).
+    pub ethernet: Option<EthernetSetting>,
+
+    /// Wi-Fi settings (`802-11-wireless`).
+    pub wireless: Option<WirelessSetting>,
+
+    /// Wi-Fi security settings (`802-11-wireless-security`).
+    pub wireless_security: Option<WirelessSecuritySetting>,
+
+    /// 802.1X authentication settings (`802-1x`), for enterprise networks.
+    pub ieee8021x: Option<Ieee8021xSetting>,
+
+    /// Bond settings (`bond`).
+    pub bond: Option<BondSetting>,
+
+    /// Bond port settings (`bond-port`).
+    pub bond_port: Option<BondPortSetting>,
+
+    /// Bridge settings (`bridge`).
+    pub bridge: Option<BridgeSetting>,
+
+    /// Bridge port settings (`bridge-port`).
+    pub bridge_port: Option<BridgePortSetting>,
+
+    /// VLAN settings (`vlan`).
+    pub vlan: Option<VlanSetting>,
+
+    /// MACVLAN settings (`macvlan`).
+    pub macvlan: Option<MacvlanSetting>,
+
+    /// VXLAN settings (`vxlan`).
+    pub vxlan: Option<VxlanSetting>,
+
+    /// WireGuard settings (`wireguard`).
+    pub wireguard: Option<WireGuardSetting>,
+
+    /// IPv4 settings.
+    pub ipv4: Option<Ipv4Setting>,
+
+    /// IPv6 settings.
+    pub ipv6: Option<Ipv6Setting>,
+
+    /// Settings not modelled by this crate, kept as-is.
+    pub other: RawSettings,
+}
+
+impl ConnectionSettings {
+    /// Create the settings of a bond on a new interface.
+    ///
+    /// Ports are attached to the bond with [`ConnectionSettings::port()`].
+    pub fn bond(interface_name: impl Into<String>, bond: BondSetting) -> Self {
+        Self {
+            bond: Some(bond),
+            ..Self::interface(interface_name.into(), BondSetting::NAME)
+        }
+    }
+
+    /// Create the settings of a bridge on a new interface.
+    ///
+    /// Ports are attached to the bridge with [`ConnectionSettings::port()`].
+    pub fn bridge(interface_name: impl Into<String>, bridge: BridgeSetting) -> Self {
+        Self {
+            bridge: Some(bridge),
+            ..Self::interface(interface_name.into(), BridgeSetting::NAME)
+        }
+    }
+
+    /// Create the settings of a VLAN on a new interface.
+    pub fn vlan(interface_name: impl Into<String>, vlan: VlanSetting) -> Self {
+        Self {
+            vlan: Some(vlan),
+            ..Self::interface(interface_name.into(), VlanSetting::NAME)
+        }
+    }
+
+    /// Create the settings of a MACVLAN on a new interface.
+    pub fn macvlan(interface_name: impl Into<String>, macvlan: MacvlanSetting) -> Self {
+        Self {
+            macvlan: Some(macvlan),
+            ..Self::interface(interface_name.into(), MacvlanSetting::NAME)
+        }
+    }
+
+    /// Create the settings of a VXLAN on a new interface.
+    pub fn vxlan(interface_name: impl Into<String>, vxlan: VxlanSetting) -> Self {
+        Self {
+            vxlan: Some(vxlan),
+            ..Self::interface(interface_name.into(), VxlanSetting::NAME)
+        }
+    }
+
+    /// Create the settings of a WireGuard tunnel on a new interface.
+    ///
+    /// ```rust,no_run
+    /// # use passcod_networkmanager::{setting::*, Error, NetworkManager};
+    /// # async fn example(nm: NetworkManager, peer_key: WireGuardPublicKey) -> Result<(), Error> {
+    /// let private_key = WireGuardPrivateKey::generate();
+    /// let wireguard = WireGuardSetting::new(&private_key)
+    ///     .with_listen_port(51820)
+    ///     .with_peer(
+    ///         WireGuardPeer::new(peer_key)
+    ///             .with_endpoint("vpn.example.com:51820")
+    ///             .with_allowed_ip("10.0.0.0".parse().unwrap(), 24),
+    ///     );
+    /// nm.settings()
+    ///     .add_connection(&ConnectionSettings::wireguard("wg0", wireguard))
+    ///     .await?;
+    /// println!("our public key: {}", private_key.public_key());
+    /// # Ok(()) }
+    /// ```
+    pub fn wireguard(interface_name: impl Into<String>, wireguard: WireGuardSetting) -> Self {
+        Self {
+            wireguard: Some(wireguard),
+            ..Self::interface(interface_name.into(), WireGuardSetting::NAME)
+        }
+    }
+
+    fn interface(interface_name: String, connection_type: &str) -> Self {
+        Self {
+            connection: Some(ConnectionSetting {
+                id: Some(interface_name.clone()),
+                connection_type: Some(connection_type.into()),
+                interface_name: Some(interface_name),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Create the settings of an Ethernet port of this bond, bridge, team or VRF.
+    ///
+    /// The port is the existing Ethernet interface `interface_name`. It refers to this controller
+    /// by UUID if one is set, or else by interface name.
+    ///
+    /// ```rust,no_run
+    /// # use passcod_networkmanager::{setting::{BondMode, BondSetting, ConnectionSettings}, Error, NetworkManager};
+    /// # async fn example(nm: NetworkManager) -> Result<(), Error> {
+    /// let bond = ConnectionSettings::bond("bond0", BondSetting::new(BondMode::ActiveBackup).with_miimon(100));
+    /// nm.settings().add_connection(&bond).await?;
+    /// for port in ["eth0", "eth1"] {
+    ///     nm.settings().add_connection(&bond.port(port)?).await?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn port(&self, interface_name: impl Into<String>) -> Result<Self, Error> {
+        let connection = self.connection.as_ref();
+        let port_type = connection
+            .and_then(|connection| connection.connection_type.clone())
+            .filter(|port_type| {
+                [BondSetting::NAME, BridgeSetting::NAME, "team", "vrf"]
+                    .contains(&port_type.as_str())
+            })
+            .ok_or_else(|| {
+                Error::InvalidSetting(format!(
+                    "{}.type must be a controller type, like bond or bridge",
+                    ConnectionSetting::NAME
+                ))
+            })?;
+        let controller = connection
+            .and_then(|connection| {
+                connection
+                    .uuid
+                    .clone()
+                    .or_else(|| connection.interface_name.clone())
+            })
+            .ok_or_else(|| {
+                Error::InvalidSetting(format!(
+                    "{}.uuid or {0}.interface-name is required",
+                    ConnectionSetting::NAME
+                ))
+            })?;
+
+        let interface_name = interface_name.into();
+        Ok(Self {
+            connection: Some(ConnectionSetting {
+                id: Some(format!("{port_type}-port-{interface_name}")),
+                connection_type: Some(EthernetSetting::NAME.into()),
+                interface_name: Some(interface_name),
+                controller: Some(controller),
+                port_type: Some(port_type),
+                ..Default::default()
+            }),
+            ethernet: Some(EthernetSetting::default()),
+            ..Default::default()
+        })
+    }
+
+    /// Parse connection settings from their D-Bus form.
+    pub fn from_dbus(raw: RawSettings) -> Result<Self, Error> {
+        let mut settings = Self::default();
+        for (name, properties) in raw {
+            match name.as_str() {
+                ConnectionSetting::NAME => {
+                    settings.connection = Some(Setting::from_dbus(properties)?)
+                }
+                EthernetSetting::NAME => settings.ethernet = Some(Setting::from_dbus(properties)?),
+                WirelessSetting::NAME => settings.wireless = Some(Setting::from_dbus(properties)?),
+                WirelessSecuritySetting::NAME => {
+                    settings.wireless_security = Some(Setting::from_dbus(properties)?)
+                }
+                Ieee8021xSetting::NAME => {
+                    settings.ieee8021x = Some(Setting::from_dbus(properties)?)
+                }
+                BondSetting::NAME => settings.bond = Some(Setting::from_dbus(properties)?),
+                BondPortSetting::NAME => settings.bond_port = Some(Setting::from_dbus(properties)?),
+                BridgeSetting::NAME => settings.bridge = Some(Setting::from_dbus(properties)?),
+                BridgePortSetting::NAME => {
+                    settings.bridge_port = Some(Setting::from_dbus(properties)?)
+                }
+                VlanSetting::NAME => settings.vlan = Some(Setting::from_dbus(properties)?),
+                MacvlanSetting::NAME => settings.macvlan = Some(Setting::from_dbus(properties)?),
+                VxlanSetting::NAME => settings.vxlan = Some(Setting::from_dbus(properties)?),
+                WireGuardSetting::NAME => {
+                    settings.wireguard = Some(Setting::from_dbus(properties)?)
+                }
+                Ipv4Setting::NAME => settings.ipv4 = Some(Setting::from_dbus(properties)?),
+                Ipv6Setting::NAME => settings.ipv6 = Some(Setting::from_dbus(properties)?),
+                _ => {
+                    settings.other.insert(name, properties);
+                }
+            }
+        }
+        Ok(settings)
+    }
+
+    /// The D-Bus signature of a property modelled by this crate.
+    pub(crate) fn signature(setting: &str, key: &str) -> Option<&'static Signature> {
+        match setting {
+            ConnectionSetting::NAME => ConnectionSetting::signature(key),
+            EthernetSetting::NAME => EthernetSetting::signature(key),
+            WirelessSetting::NAME => WirelessSetting::signature(key),
+            WirelessSecuritySetting::NAME => WirelessSecuritySetting::signature(key),
+            Ieee8021xSetting::NAME => Ieee8021xSetting::signature(key),
+            BondSetting::NAME => BondSetting::signature(key),
+            BondPortSetting::NAME => BondPortSetting::signature(key),
+            BridgeSetting::NAME => BridgeSetting::signature(key),
+            BridgePortSetting::NAME => BridgePortSetting::signature(key),
+            VlanSetting::NAME => VlanSetting::signature(key),
+            MacvlanSetting::NAME => MacvlanSetting::signature(key),
+            VxlanSetting::NAME => VxlanSetting::signature(key),
+            WireGuardSetting::NAME => WireGuardSetting::signature(key),
+            Ipv4Setting::NAME => Ipv4Setting::signature(key),
+            Ipv6Setting::NAME => Ipv6Setting::signature(key),
+            _ => None,
+        }
+    }
+
+    /// Check the settings for mistakes that NetworkManager would reject.
+    ///
+    /// This is not exhaustive: it checks the constraints that this crate models, like the length
+    /// of a Wi-Fi PSK, so that they can be reported before the settings are sent over D-Bus.
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(wireless) = &self.wireless {
+            wireless.validate()?;
+        }
+        if let Some(security) = &self.wireless_security {
+            security.validate()?;
+        }
+        if let Some(ieee8021x) = &self.ieee8021x {
+            ieee8021x.validate()?;
+        }
+        if let Some(vlan) = &self.vlan {
+            vlan.validate()?;
+        }
+        if let Some(vxlan) = &self.vxlan {
+            vxlan.validate()?;
+        }
+        if let Some(wireguard) = &self.wireguard {
+            wireguard.validate()?;
+        }
+        if let Some(ipv4) = &self.ipv4 {
+            ipv4.validate()?;
+        }
+        if let Some(ipv6) = &self.ipv6 {
+            ipv6.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Convert connection settings to their D-Bus form.
+    ///
+    /// Fails if a value kept as-is, in `other`, `extra` or `attributes`, can't be copied, like a
+    /// file descriptor that can't be duplicated.
+    pub fn to_dbus(&self) -> Result<RawSettings, Error> {
+        fn insert<S: Setting>(raw: &mut RawSettings, setting: &Option<S>) -> Result<(), Error> {
+            if let Some(setting) = setting {
+                raw.insert(S::NAME.into(), setting.to_dbus()?);
+            }
+            Ok(())
+        }
+
+        let mut raw: RawSettings = self
+            .other
+            .iter()
+            .map(|(name, properties)| Ok((name.clone(), clone_properties(properties)?)))
+            .collect::<Result<_, Error>>()?;
+        insert(&mut raw, &self.connection)?;
+        insert(&mut raw, &self.ethernet)?;
+        insert(&mut raw, &self.wireless)?;
+        insert(&mut raw, &self.wireless_security)?;
+        insert(&mut raw, &self.ieee8021x)?;
+        insert(&mut raw, &self.bond)?;
+        insert(&mut raw, &self.bond_port)?;
+        insert(&mut raw, &self.bridge)?;
+        insert(&mut raw, &self.bridge_port)?;
+        insert(&mut raw, &self.vlan)?;
+        insert(&mut raw, &self.macvlan)?;
+        insert(&mut raw, &self.vxlan)?;
+        insert(&mut raw, &self.wireguard)?;
+        insert(&mut raw, &self.ipv4)?;
+        insert(&mut raw, &self.ipv6)?;
+        Ok(raw)
+    }
+}
+
+/// Borrow settings in the form the raw proxies take them.
+pub(crate) fn borrow_dbus(
+    raw: &RawSettings,
+) -> Result<HashMap<&str, HashMap<&str, Value<'_>>>, Error> {
+    raw.iter()
+        .map(|(name, properties)| {
+            let properties = properties
+                .iter()
+                .map(|(key, value)| Ok((key.as_str(), Value::try_from(value)?)))
+                .collect::<Result<_, zbus::zvariant::Error>>()
+                .map_err(zbus::Error::from)?;
+            Ok((name.as_str(), properties))
+        })
+        .collect()
+}
+
+fn clone_properties(
+    properties: &HashMap<String, OwnedValue>,
+) -> Result<HashMap<String, OwnedValue>, Error> {
+    properties
+        .iter()
+        .map(|(key, value)| Ok((key.clone(), clone_value(value)?)))
+        .collect()
+}
+
+/// Copy a setting value.
+///
+/// Only values holding a file descriptor can fail to copy. NetworkManager never sends those, but
+/// callers can put any value in `other`, `extra` or `attributes`.
+fn clone_value(value: &OwnedValue) -> Result<OwnedValue, Error> {
+    value.try_clone().map_err(uncopyable)
+}
+
+fn owned_value(value: Value<'_>) -> Result<OwnedValue, Error> {
+    OwnedValue::try_from(value).map_err(uncopyable)
+}
+
+fn uncopyable(error: zbus::zvariant::Error) -> Error {
+    Error::InvalidSetting(format!("setting value can't be copied: {error}"))
+}
+
+/// A type that can be the value of a setting property.
+pub(crate) trait SettingValue: Sized {
+    /// The D-Bus signature of the value.
+    const SIGNATURE: &'static Signature;
+
+    fn from_value(value: OwnedValue) -> Option<Self>;
+    fn to_value(&self) -> Result<OwnedValue, Error>;
+}
+
+macro_rules! setting_value {
+    ($($ty:ty),+) => {
+        $(
+            impl SettingValue for $ty {
+                const SIGNATURE: &'static Signature = <$ty as Type>::SIGNATURE;
+
+                fn from_value(value: OwnedValue) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+
+                fn to_value(&self) -> Result<OwnedValue, Error> {
+                    owned_value(Value::from(self.clone()))
+                }
+            }
+        )+
+    };
+}
+
+setting_value!(
+    bool,
+    i32,
+    u32,
+    i64,
+    u64,
+    String,
+    Vec<u8>,
+    Vec<u32>,
+    Vec<String>
+);
+
+impl SettingValue for Vec<Vec<u8>> {
+    const SIGNATURE: &'static Signature = <Vec<Vec<u8>> as Type>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        match Value::from(value) {
+            Value::Array(array) => array
+                .iter()
+                .map(|item| Vec::<u8>::try_from(item.try_clone().ok()?).ok())
+                .collect(),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        owned_value(Value::from(self.clone()))
+    }
+}
+
+impl SettingValue for SecretFlags {
+    const SIGNATURE: &'static Signature = <u32 as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        u32::try_from(value).ok().map(SecretFlags::from_bits_retain)
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        Ok(self.bits().into())
+    }
+}
+
+impl SettingValue for HashMap<String, String> {
+    const SIGNATURE: &'static Signature = <HashMap<String, String> as Type>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        HashMap::try_from(value).ok()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        Ok(OwnedValue::from(self.clone()))
+    }
+}
+
+/// Take a property out of a setting's D-Bus map and parse it.
+///
+/// A value that can't be parsed, like an enum value this crate doesn't know, is left in the map so
+/// that it ends up in the `extra` field and is sent back unchanged.
+pub(crate) fn take<T: SettingValue>(
+    properties: &mut HashMap<String, OwnedValue>,
+    key: &str,
+) -> Option<T> {
+    let value = T::from_value(clone_value(properties.get(key)?).ok()?)?;
+    properties.remove(key);
+    Some(value)
+}
+
+/// Define a setting struct with typed, optional properties.
+///
+/// Properties not listed, and values of listed properties that can't be parsed, are kept in the
+/// `extra` field.
+macro_rules! setting {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident = $setting:literal {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident: $ty:ty = $key:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, PartialEq)]
+        pub struct $name {
+            $(
+                $(#[$field_meta])*
+                #[doc = ""]
+                #[doc = concat!("D-Bus property: `", $key, "`.")]
+                pub $field: Option<$ty>,
+            )*
+
+            /// Properties not modelled by this crate, kept as-is.
+            pub extra: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+        }
+
+        impl $name {
+            /// The D-Bus signature of a property modelled by this setting.
+            pub(crate) fn signature(key: &str) -> Option<&'static zbus::zvariant::Signature> {
+                match key {
+                    $($key => Some(<$ty as crate::setting::SettingValue>::SIGNATURE),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl crate::setting::Setting for $name {
+            const NAME: &'static str = $setting;
+
+            #[allow(unused_mut)]
+            fn from_dbus(
+                mut properties: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+            ) -> Result<Self, crate::Error> {
+                Ok(Self {
+                    $($field: crate::setting::take(&mut properties, $key),)*
+                    extra: properties,
+                })
+            }
+
+            fn to_dbus(
+                &self,
+            ) -> Result<std::collections::HashMap<String, zbus::zvariant::OwnedValue>, crate::Error>
+            {
+                #[allow(unused_mut)]
+                let mut properties = crate::setting::clone_properties(&self.extra)?;
+                $(
+                    if let Some(value) = &self.$field {
+                        properties.insert(
+                            $key.into(),
+                            crate::setting::SettingValue::to_value(value)?,
+                        );
+                    }
+                )*
+                Ok(properties)
+            }
+        }
+    };
+}
+
+/// Define an enum for a string-valued setting property.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $value:literal,
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )+
+        }
+
+        impl $name {
+            /// The value of this variant in the D-Bus form.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $value,)+
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = crate::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($value => Ok($name::$variant),)+
+                    _ => Err(crate::Error::InvalidSetting(format!(
+                        "unknown {} value: {s}",
+                        stringify!($name)
+                    ))),
+                }
+            }
+        }
+
+        impl crate::setting::SettingValue for $name {
+            const SIGNATURE: &'static zbus::zvariant::Signature =
+                <String as crate::setting::SettingValue>::SIGNATURE;
+
+            fn from_value(value: zbus::zvariant::OwnedValue) -> Option<Self> {
+                String::try_from(value).ok()?.parse().ok()
+            }
+
+            fn to_value(&self) -> Result<zbus::zvariant::OwnedValue, crate::Error> {
+                Ok(zbus::zvariant::Str::from_static(self.as_str()).into())
+            }
+        }
+    };
+}
+
+pub(crate) use setting;
+pub(crate) use string_enum;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_values_round_trip() {
+        let raw: RawSettings = [
+            (
+                ConnectionSetting::NAME.to_owned(),
+                HashMap::from([
+                    ("id".to_owned(), "Mesh".to_owned().to_value().unwrap()),
+                    ("multi-connect".to_owned(), 99i32.to_value().unwrap()),
+                ]),
+            ),
+            (
+                WirelessSecuritySetting::NAME.to_owned(),
+                HashMap::from([
+                    (
+                        "key-mgmt".to_owned(),
+                        "wpa-none".to_owned().to_value().unwrap(),
+                    ),
+                    ("psk".to_owned(), "hunter22".to_owned().to_value().unwrap()),
+                ]),
+            ),
+            (
+                Ieee8021xSetting::NAME.to_owned(),
+                HashMap::from([(
+                    "eap".to_owned(),
+                    vec!["peap".to_owned(), "external".to_owned()]
+                        .to_value()
+                        .unwrap(),
+                )]),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let settings = ConnectionSettings::from_dbus(
+            raw.iter()
+                .map(|(name, properties)| (name.clone(), clone_properties(properties).unwrap()))
+                .collect(),
+        )
+        .unwrap();
+
+        let connection = settings.connection.as_ref().unwrap();
+        assert_eq!(connection.id.as_deref(), Some("Mesh"));
+        assert_eq!(connection.multi_connect, None);
+        assert!(connection.extra.contains_key("multi-connect"));
+
+        let security = settings.wireless_security.as_ref().unwrap();
+        assert_eq!(security.key_mgmt, None);
+        assert_eq!(security.psk.as_deref(), Some("hunter22"));
+        assert!(security.extra.contains_key("key-mgmt"));
+
+        assert_eq!(settings.ieee8021x.as_ref().unwrap().eap, None);
+        assert_eq!(settings.to_dbus().unwrap(), raw);
+    }
+
+    #[test]
+    fn controllers_and_ports() {
+        let bond = ConnectionSettings::bond("bond0", BondSetting::new(BondMode::ActiveBackup));
+        let connection = bond.connection.as_ref().unwrap();
+        assert_eq!(connection.id.as_deref(), Some("bond0"));
+        assert_eq!(connection.interface_name.as_deref(), Some("bond0"));
+        assert_eq!(
+            connection.connection_type.as_deref(),
+            Some(BondSetting::NAME)
+        );
+        assert!(bond.bond.is_some());
+
+        // without a UUID, ports refer to the controller by interface name
+        let port = bond.port("eth0").unwrap();
+        let connection = port.connection.as_ref().unwrap();
+        assert_eq!(connection.id.as_deref(), Some("bond-port-eth0"));
+        assert_eq!(connection.interface_name.as_deref(), Some("eth0"));
+        assert_eq!(
+            connection.connection_type.as_deref(),
+            Some(EthernetSetting::NAME)
+        );
+        assert_eq!(connection.controller.as_deref(), Some("bond0"));
+        assert_eq!(connection.port_type.as_deref(), Some(BondSetting::NAME));
+        assert!(port.ethernet.is_some());
+        assert_eq!(
+            ConnectionSettings::from_dbus(port.to_dbus().unwrap()).unwrap(),
+            port
+        );
+
+        let mut bridge = ConnectionSettings::bridge("br0", BridgeSetting::default());
+        assert!(bridge.bridge.is_some());
+        let uuid = "3f0a8e49-7c2c-4a3c-9d1e-6b2f1c0e9a7d";
+        bridge.connection.as_mut().unwrap().uuid = Some(uuid.into());
+        let port = bridge.port("eth1").unwrap();
+        let connection = port.connection.as_ref().unwrap();
+        assert_eq!(connection.controller.as_deref(), Some(uuid));
+        assert_eq!(connection.port_type.as_deref(), Some(BridgeSetting::NAME));
+    }
+
+    #[test]
+    fn ports_need_a_controller() {
+        let ethernet = ConnectionSettings {
+            connection: Some(ConnectionSetting {
+                connection_type: Some(EthernetSetting::NAME.into()),
+                interface_name: Some("eth0".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(ethernet.port("eth1").is_err());
+        assert!(ConnectionSettings::default().port("eth1").is_err());
+
+        let mut bond = ConnectionSettings::bond("bond0", BondSetting::default());
+        bond.connection.as_mut().unwrap().interface_name = None;
+        assert!(bond.port("eth1").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use crate::Error;
+
+use super::{setting, string_enum, Setting};
+
+setting! {
+    /// Bond settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-bond.html)
+    ///
+    /// The bond is configured with the options of the kernel's bonding driver, as strings. The
+    /// common ones have typed accessors and builders.
+    pub struct BondSetting = "bond" {
+        /// The bonding options, like `mode` or `miimon`, as understood by the bonding driver.
+        options: HashMap<String, String> = "options",
+    }
+}
+
+impl BondSetting {
+    /// Create bond settings with the given mode.
+    pub fn new(mode: BondMode) -> Self {
+        Self::default().with_option("mode", mode.as_str())
+    }
+
+    /// Set a bonding option.
+    pub fn with_option(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the link monitoring interval, in milliseconds.
+    pub fn with_miimon(self, interval: u32) -> Self {
+        self.with_option("miimon", interval.to_string())
+    }
+
+    /// Set the rate at which LACPDUs are requested from the partner, in 802.3ad mode.
+    pub fn with_lacp_rate(self, rate: LacpRate) -> Self {
+        self.with_option("lacp_rate", rate.as_str())
+    }
+
+    /// Set how the port to send a packet on is chosen, in balance-xor, 802.3ad and balance-tlb
+    /// modes.
+    pub fn with_xmit_hash_policy(self, policy: XmitHashPolicy) -> Self {
+        self.with_option("xmit_hash_policy", policy.as_str())
+    }
+
+    /// Get a bonding option.
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options.as_ref()?.get(key).map(String::as_str)
+    }
+
+    /// The bonding mode.
+    ///
+    /// The kernel also accepts the numeric form of modes, which this parses too.
+    pub fn mode(&self) -> Result<Option<BondMode>, Error> {
+        self.option("mode")
+            .map(|mode| match mode {
+                "0" => Ok(BondMode::BalanceRr),
+                "1" => Ok(BondMode::ActiveBackup),
+                "2" => Ok(BondMode::BalanceXor),
+                "3" => Ok(BondMode::Broadcast),
+                "4" => Ok(BondMode::Ieee8023ad),
+                "5" => Ok(BondMode::BalanceTlb),
+                "6" => Ok(BondMode::BalanceAlb),
+                mode => mode.parse(),
+            })
+            .transpose()
+    }
+
+    /// The link monitoring interval, in milliseconds.
+    pub fn miimon(&self) -> Result<Option<u32>, Error> {
+        self.option("miimon")
+            .map(|interval| {
+                interval.parse().map_err(|_| {
+                    Error::InvalidSetting(format!("{}.options.miimon is not a number", Self::NAME))
+                })
+            })
+            .transpose()
+    }
+
+    /// The rate at which LACPDUs are requested from the partner.
+    pub fn lacp_rate(&self) -> Result<Option<LacpRate>, Error> {
+        self.option("lacp_rate")
+            .map(|rate| match rate {
+                "0" => Ok(LacpRate::Slow),
+                "1" => Ok(LacpRate::Fast),
+                rate => rate.parse(),
+            })
+            .transpose()
+    }
+
+    /// How the port to send a packet on is chosen.
+    pub fn xmit_hash_policy(&self) -> Result<Option<XmitHashPolicy>, Error> {
+        self.option("xmit_hash_policy")
+            .map(|policy| match policy {
+                "0" => Ok(XmitHashPolicy::Layer2),
+                "1" => Ok(XmitHashPolicy::Layer3_4),
+                "2" => Ok(XmitHashPolicy::Layer2_3),
+                "3" => Ok(XmitHashPolicy::Encap2_3),
+                "4" => Ok(XmitHashPolicy::Encap3_4),
+                "5" => Ok(XmitHashPolicy::VlanSrcMac),
+                policy => policy.parse(),
+            })
+            .transpose()
+    }
+}
+
+string_enum! {
+    /// The mode of a bond.
+    pub enum BondMode {
+        /// Round-robin across ports.
+        BalanceRr = "balance-rr",
+
+        /// Only one port is active, the others take over if it fails.
+        ActiveBackup = "active-backup",
+
+        /// Ports are chosen by hashing packets, see [`XmitHashPolicy`].
+        BalanceXor = "balance-xor",
+
+        /// Every packet is sent on all ports.
+        Broadcast = "broadcast",
+
+        /// IEEE 802.3ad dynamic link aggregation (LACP).
+        Ieee8023ad = "802.3ad",
+
+        /// Adaptive transmit load balancing.
+        BalanceTlb = "balance-tlb",
+
+        /// Adaptive transmit and receive load balancing.
+        BalanceAlb = "balance-alb",
+    }
+}
+
+string_enum! {
+    /// The rate at which LACPDUs are requested from the link partner of an 802.3ad bond.
+    pub enum LacpRate {
+        /// Every 30 seconds.
+        Slow = "slow",
+
+        /// Every second.
+        Fast = "fast",
+    }
+}
+
+string_enum! {
+    /// How a bond chooses the port to send a packet on.
+    pub enum XmitHashPolicy {
+        /// Hash the MAC addresses.
+        Layer2 = "layer2",
+
+        /// Hash the MAC and IP addresses.
+        Layer2_3 = "layer2+3",
+
+        /// Hash the IP addresses and ports.
+        Layer3_4 = "layer3+4",
+
+        /// Hash the MAC and IP addresses, looking inside tunnels.
+        Encap2_3 = "encap2+3",
+
+        /// Hash the IP addresses and ports, looking inside tunnels.
+        Encap3_4 = "encap3+4",
+
+        /// Hash the VLAN ID and source MAC address.
+        VlanSrcMac = "vlan+srcmac",
+    }
+}
+
+setting! {
+    /// Settings of a bond port.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-bond-port.html)
+    pub struct BondPortSetting = "bond-port" {
+        /// The queue ID of the port.
+        queue_id: u32 = "queue-id",
+
+        /// The priority of the port, in active-backup, balance-tlb and balance-alb modes.
+        prio: i32 = "prio",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_round_trip() {
+        let bond = BondSetting::new(BondMode::Ieee8023ad)
+            .with_miimon(100)
+            .with_lacp_rate(LacpRate::Fast)
+            .with_xmit_hash_policy(XmitHashPolicy::Layer3_4)
+            .with_option("updelay", "200");
+        let bond = BondSetting::from_dbus(bond.to_dbus().unwrap()).unwrap();
+
+        assert_eq!(bond.option("mode"), Some("802.3ad"));
+        assert_eq!(bond.option("updelay"), Some("200"));
+        assert_eq!(bond.mode().unwrap(), Some(BondMode::Ieee8023ad));
+        assert_eq!(bond.miimon().unwrap(), Some(100));
+        assert_eq!(bond.lacp_rate().unwrap(), Some(LacpRate::Fast));
+        assert_eq!(
+            bond.xmit_hash_policy().unwrap(),
+            Some(XmitHashPolicy::Layer3_4)
+        );
+    }
+
+    #[test]
+    fn numeric_options() {
+        let option = |key: &str, value: &str| BondSetting::default().with_option(key, value);
+
+        for (value, mode) in [
+            ("0", BondMode::BalanceRr),
+            ("1", BondMode::ActiveBackup),
+            ("2", BondMode::BalanceXor),
+            ("3", BondMode::Broadcast),
+            ("4", BondMode::Ieee8023ad),
+            ("5", BondMode::BalanceTlb),
+            ("6", BondMode::BalanceAlb),
+        ] {
+            assert_eq!(option("mode", value).mode().unwrap(), Some(mode));
+        }
+        assert!(option("mode", "7").mode().is_err());
+
+        assert_eq!(
+            option("lacp_rate", "0").lacp_rate().unwrap(),
+            Some(LacpRate::Slow)
+        );
+        assert_eq!(
+            option("lacp_rate", "1").lacp_rate().unwrap(),
+            Some(LacpRate::Fast)
+        );
+
+        for (value, policy) in [
+            ("0", XmitHashPolicy::Layer2),
+            ("1", XmitHashPolicy::Layer3_4),
+            ("2", XmitHashPolicy::Layer2_3),
+            ("3", XmitHashPolicy::Encap2_3),
+            ("4", XmitHashPolicy::Encap3_4),
+            ("5", XmitHashPolicy::VlanSrcMac),
+        ] {
+            assert_eq!(
+                option("xmit_hash_policy", value)
+                    .xmit_hash_policy()
+                    .unwrap(),
+                Some(policy)
+            );
+        }
+        assert!(option("xmit_hash_policy", "6").xmit_hash_policy().is_err());
+    }
+
+    #[test]
+    fn missing_and_invalid_options() {
+        let bond = BondSetting::default();
+        assert_eq!(bond.mode().unwrap(), None);
+        assert_eq!(bond.miimon().unwrap(), None);
+        assert_eq!(bond.lacp_rate().unwrap(), None);
+        assert_eq!(bond.xmit_hash_policy().unwrap(), None);
+
+        let bond = bond.with_option("miimon", "often");
+        assert!(bond.miimon().is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use super::setting;
+
+setting! {
+    /// Bridge settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-bridge.html)
+    pub struct BridgeSetting = "bridge" {
+        /// The MAC address of the bridge.
+        ///
+        /// If None, the bridge uses the MAC address of one of its ports.
+        mac_address: Vec<u8> = "mac-address",
+
+        /// Whether the Spanning Tree Protocol is enabled.
+        stp: bool = "stp",
+
+        /// The STP priority of the bridge. Lower values are more likely to become the root bridge.
+        priority: u32 = "priority",
+
+        /// The STP forwarding delay, in seconds.
+        forward_delay: u32 = "forward-delay",
+
+        /// The STP hello time, in seconds.
+        hello_time: u32 = "hello-time",
+
+        /// The STP maximum message age, in seconds.
+        max_age: u32 = "max-age",
+
+        /// How long learned MAC addresses are kept in the forwarding database, in seconds.
+        ageing_time: u32 = "ageing-time",
+
+        /// Whether IGMP snooping is enabled.
+        multicast_snooping: bool = "multicast-snooping",
+
+        /// Whether VLAN filtering is enabled.
+        vlan_filtering: bool = "vlan-filtering",
+
+        /// The default port VLAN ID, when VLAN filtering is enabled. Zero means none.
+        vlan_default_pvid: u32 = "vlan-default-pvid",
+    }
+}
+
+impl BridgeSetting {
+    /// Enable or disable the Spanning Tree Protocol.
+    pub fn with_stp(mut self, enabled: bool) -> Self {
+        self.stp = Some(enabled);
+        self
+    }
+
+    /// Set the STP priority of the bridge.
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Enable or disable VLAN filtering.
+    pub fn with_vlan_filtering(mut self, enabled: bool) -> Self {
+        self.vlan_filtering = Some(enabled);
+        self
+    }
+
+    /// Set the default port VLAN ID, used with VLAN filtering.
+    pub fn with_vlan_default_pvid(mut self, pvid: u32) -> Self {
+        self.vlan_default_pvid = Some(pvid);
+        self
+    }
+}
+
+setting! {
+    /// Settings of a bridge port.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-bridge-port.html)
+    pub struct BridgePortSetting = "bridge-port" {
+        /// The STP priority of the port.
+        priority: u32 = "priority",
+
+        /// The STP cost of the port.
+        path_cost: u32 = "path-cost",
+
+        /// Whether frames may be sent back out of the port they were received on.
+        hairpin_mode: bool = "hairpin-mode",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setting::Setting;
+
+    #[test]
+    fn builders_round_trip() {
+        let bridge = BridgeSetting::default()
+            .with_stp(false)
+            .with_priority(4096)
+            .with_vlan_filtering(true)
+            .with_vlan_default_pvid(10);
+        let parsed = BridgeSetting::from_dbus(bridge.to_dbus().unwrap()).unwrap();
+        assert_eq!(parsed, bridge);
+        assert_eq!(parsed.stp, Some(false));
+        assert_eq!(parsed.priority, Some(4096));
+        assert_eq!(parsed.vlan_filtering, Some(true));
+        assert_eq!(parsed.vlan_default_pvid, Some(10));
+    }
+}
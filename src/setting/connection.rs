@@ -0,0 +1,122 @@
+use super::{setting, SettingValue};
+
+setting! {
+    /// General settings, common to all connection types.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-connection.html)
+    pub struct ConnectionSetting = "connection" {
+        /// A human readable unique identifier for the connection, like "Work Wi-Fi".
+        id: String = "id",
+
+        /// A universally unique identifier for the connection.
+        ///
+        /// This is generated by NetworkManager if absent when the connection is added.
+        uuid: String = "uuid",
+
+        /// Base type of the connection.
+        ///
+        /// This is the name of the setting that describes the connection's base type, eg
+        /// `802-3-ethernet` or `802-11-wireless`.
+        connection_type: String = "type",
+
+        /// The name of the network interface this connection is bound to.
+        ///
+        /// If absent, the connection can be attached to any interface of the appropriate type.
+        interface_name: String = "interface-name",
+
+        /// Whether the connection should be automatically connected when resources are available.
+        autoconnect: bool = "autoconnect",
+
+        /// The autoconnect priority, in range -999 to 999.
+        ///
+        /// When multiple connections are eligible for autoconnecting, the one with the higher
+        /// priority is preferred.
+        autoconnect_priority: i32 = "autoconnect-priority",
+
+        /// The number of times the connection should be tried when autoactivating before giving up.
+        ///
+        /// Zero means forever, -1 means the global default.
+        autoconnect_retries: i32 = "autoconnect-retries",
+
+        /// Whether to autoconnect the ports of this connection when it is activated.
+        ///
+        /// -1 means the global default, 0 means no, 1 means yes.
+        autoconnect_ports: i32 = "autoconnect-slaves",
+
+        /// The interface name or UUID of the controller connection, if this is a port.
+        controller: String = "master",
+
+        /// The setting name of the controller's type, if this is a port, eg `bond` or `bridge`.
+        port_type: String = "slave-type",
+
+        /// The firewalld zone the connection's interface should be placed in.
+        zone: String = "zone",
+
+        /// Whether the connection is metered.
+        ///
+        /// 0 means unknown (guess), 1 means yes, 2 means no. See
+        /// [`MeteredSetting`](crate::types::MeteredSetting).
+        metered: i32 = "metered",
+
+        /// Whether multiple devices may activate this connection at the same time.
+        multi_connect: MultiConnect = "multi-connect",
+
+        /// Users allowed to activate the connection, in the form `user:<username>[:<reserved>]`.
+        ///
+        /// If empty, all users are allowed.
+        permissions: Vec<String> = "permissions",
+
+        /// UUIDs of secondary connections (usually VPNs) to activate alongside this one.
+        secondaries: Vec<String> = "secondaries",
+
+        /// A token used to generate stable identifiers, like stable MAC addresses or IPv6
+        /// addresses.
+        stable_id: String = "stable-id",
+
+        /// The time, in seconds since the Unix epoch, that the connection was last fully
+        /// activated.
+        timestamp: u64 = "timestamp",
+
+        /// How long, in seconds, to wait for the device to be activated before failing.
+        ///
+        /// -1 means the global default.
+        wait_device_timeout: i32 = "wait-device-timeout",
+    }
+}
+
+/// Whether a connection can be active on multiple devices at once.
+///
+/// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMConnectionMultiConnect)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MultiConnect {
+    /// Use the global default, which is [`MultiConnect::Single`] unless configured otherwise.
+    Default = 0,
+
+    /// The connection can be active only once at a time.
+    Single = 1,
+
+    /// The connection can be active multiple times, but only when explicitly activated by the
+    /// user.
+    ManualMultiple = 2,
+
+    /// The connection can be active multiple times, including when autoconnecting.
+    Multiple = 3,
+}
+
+impl SettingValue for MultiConnect {
+    const SIGNATURE: &'static zbus::zvariant::Signature = <i32 as SettingValue>::SIGNATURE;
+
+    fn from_value(value: zbus::zvariant::OwnedValue) -> Option<Self> {
+        match i32::try_from(value).ok()? {
+            0 => Some(MultiConnect::Default),
+            1 => Some(MultiConnect::Single),
+            2 => Some(MultiConnect::ManualMultiple),
+            3 => Some(MultiConnect::Multiple),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Result<zbus::zvariant::OwnedValue, crate::Error> {
+        Ok((*self as i32).into())
+    }
+}
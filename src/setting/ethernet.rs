@@ -0,0 +1,41 @@
+use super::setting;
+
+setting! {
+    /// Wired Ethernet settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-802-3-ethernet.html)
+    pub struct EthernetSetting = "802-3-ethernet" {
+        /// The permanent MAC address of the device this connection is locked to.
+        mac_address: Vec<u8> = "mac-address",
+
+        /// The MAC address to set on the device when activating, or one of the special values
+        /// `preserve`, `permanent`, `random` or `stable`.
+        assigned_mac_address: String = "assigned-mac-address",
+
+        /// MAC addresses of devices this connection must not apply to, as `XX:XX:XX:XX:XX:XX`.
+        mac_address_denylist: Vec<String> = "mac-address-blacklist",
+
+        /// The MTU to set on the device, in bytes. Zero means the default.
+        mtu: u32 = "mtu",
+
+        /// Whether to enable link auto-negotiation.
+        ///
+        /// If disabled, [`speed`](Self::speed) and [`duplex`](Self::duplex) are forced.
+        auto_negotiate: bool = "auto-negotiate",
+
+        /// The link speed to force or advertise, in Mb/s.
+        speed: u32 = "speed",
+
+        /// The duplex mode to force or advertise: `half` or `full`.
+        duplex: String = "duplex",
+
+        /// The Wake-on-LAN options, as a bitfield of `NMSettingWiredWakeOnLan` values.
+        wake_on_lan: u32 = "wake-on-lan",
+
+        /// The Wake-on-LAN password, as a MAC address.
+        wake_on_lan_password: String = "wake-on-lan-password",
+
+        /// The s390 subchannels of the device, for s390 hardware.
+        s390_subchannels: Vec<String> = "s390-subchannels",
+    }
+}
@@ -0,0 +1,440 @@
+use std::{
+    ffi::OsStr,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+};
+
+use zbus::zvariant::{OwnedValue, Signature};
+
+use crate::{types::SecretFlags, Error};
+
+use super::{setting, string_enum, Setting, SettingValue};
+
+setting! {
+    /// IEEE 802.1X authentication settings, for enterprise Wi-Fi and wired networks.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-802-1x.html)
+    ///
+    /// For Wi-Fi, this is used along a [`WirelessSecuritySetting`](super::WirelessSecuritySetting)
+    /// with [`KeyManagement::WpaEap`](super::KeyManagement::WpaEap).
+    pub struct Ieee8021xSetting = "802-1x" {
+        /// The EAP methods to try, in order.
+        eap: Vec<EapMethod> = "eap",
+
+        /// The identity to authenticate as, eg a username.
+        identity: String = "identity",
+
+        /// The identity to send unencrypted before the tunnel is established, with tunnelled
+        /// methods like PEAP and TTLS.
+        anonymous_identity: String = "anonymous-identity",
+
+        /// The password, for password-based methods.
+        password: String = "password",
+
+        /// How the [`password`](Self::password) is stored.
+        password_flags: SecretFlags = "password-flags",
+
+        /// The PEAP version to use, `0` or `1`.
+        ///
+        /// If None, it is negotiated with the server.
+        peap_version: String = "phase1-peapver",
+
+        /// The authentication method to use inside the tunnel of PEAP or TTLS, with non-EAP
+        /// methods for TTLS.
+        phase2_auth: Phase2Auth = "phase2-auth",
+
+        /// The EAP method to use inside the tunnel of TTLS.
+        phase2_autheap: Phase2Auth = "phase2-autheap",
+
+        /// The certificate of the authority that signed the server's certificate.
+        ///
+        /// If None, and [`system_ca_certs`](Self::system_ca_certs) is not set, the server
+        /// certificate is not verified, which is insecure.
+        ca_cert: Certificate = "ca-cert",
+
+        /// The password for the [`ca_cert`](Self::ca_cert), when it is a PKCS#11 URI.
+        ca_cert_password: String = "ca-cert-password",
+
+        /// How the [`ca_cert_password`](Self::ca_cert_password) is stored.
+        ca_cert_password_flags: SecretFlags = "ca-cert-password-flags",
+
+        /// Whether to use the system's CA certificates to verify the server.
+        system_ca_certs: bool = "system-ca-certs",
+
+        /// The certificate of the client, for TLS.
+        client_cert: Certificate = "client-cert",
+
+        /// The password for the [`client_cert`](Self::client_cert), when it is a PKCS#11 URI.
+        client_cert_password: String = "client-cert-password",
+
+        /// How the [`client_cert_password`](Self::client_cert_password) is stored.
+        client_cert_password_flags: SecretFlags = "client-cert-password-flags",
+
+        /// The private key of the client, for TLS.
+        private_key: Certificate = "private-key",
+
+        /// The password to decrypt the [`private_key`](Self::private_key).
+        private_key_password: String = "private-key-password",
+
+        /// How the [`private_key_password`](Self::private_key_password) is stored.
+        private_key_password_flags: SecretFlags = "private-key-password-flags",
+
+        /// The CA certificate for TLS inside the tunnel.
+        phase2_ca_cert: Certificate = "phase2-ca-cert",
+
+        /// The client certificate for TLS inside the tunnel.
+        phase2_client_cert: Certificate = "phase2-client-cert",
+
+        /// The client private key for TLS inside the tunnel.
+        phase2_private_key: Certificate = "phase2-private-key",
+
+        /// The password to decrypt the [`phase2_private_key`](Self::phase2_private_key).
+        phase2_private_key_password: String = "phase2-private-key-password",
+
+        /// How the [`phase2_private_key_password`](Self::phase2_private_key_password) is stored.
+        phase2_private_key_password_flags: SecretFlags = "phase2-private-key-password-flags",
+
+        /// Domain suffixes the server certificate must match, separated by `;`.
+        ///
+        /// A suffix matches the domain itself and any of its subdomains.
+        domain_suffix_match: String = "domain-suffix-match",
+
+        /// Domains the server certificate must match exactly, separated by `;`.
+        domain_match: String = "domain-match",
+
+        /// The PIN of the smart card, for PKCS#11 certificates.
+        pin: String = "pin",
+
+        /// How the [`pin`](Self::pin) is stored.
+        pin_flags: SecretFlags = "pin-flags",
+
+        /// The timeout of the authentication, in seconds. Zero means the default.
+        auth_timeout: i32 = "auth-timeout",
+    }
+}
+
+impl Ieee8021xSetting {
+    /// Create settings for EAP-TLS, authenticating with a client certificate.
+    pub fn tls(
+        identity: impl Into<String>,
+        client_cert: Certificate,
+        private_key: Certificate,
+    ) -> Self {
+        Self {
+            eap: Some(vec![EapMethod::Tls]),
+            identity: Some(identity.into()),
+            client_cert: Some(client_cert),
+            private_key: Some(private_key),
+            ..Default::default()
+        }
+    }
+
+    /// Create settings for PEAP, authenticating with a password using MSCHAPv2 in the tunnel.
+    pub fn peap(identity: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            eap: Some(vec![EapMethod::Peap]),
+            identity: Some(identity.into()),
+            password: Some(password.into()),
+            phase2_auth: Some(Phase2Auth::Mschapv2),
+            ..Default::default()
+        }
+    }
+
+    /// Create settings for TTLS, authenticating with a password using `phase2` in the tunnel.
+    pub fn ttls(
+        identity: impl Into<String>,
+        password: impl Into<String>,
+        phase2: Phase2Auth,
+    ) -> Self {
+        Self {
+            eap: Some(vec![EapMethod::Ttls]),
+            identity: Some(identity.into()),
+            password: Some(password.into()),
+            phase2_auth: Some(phase2),
+            ..Default::default()
+        }
+    }
+
+    /// Create settings for EAP-pwd, authenticating with a password.
+    pub fn pwd(identity: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            eap: Some(vec![EapMethod::Pwd]),
+            identity: Some(identity.into()),
+            password: Some(password.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the anonymous outer identity.
+    pub fn with_anonymous_identity(mut self, identity: impl Into<String>) -> Self {
+        self.anonymous_identity = Some(identity.into());
+        self
+    }
+
+    /// Verify the server certificate against this CA certificate.
+    pub fn with_ca_cert(mut self, ca_cert: Certificate) -> Self {
+        self.ca_cert = Some(ca_cert);
+        self
+    }
+
+    /// Require the server certificate to match this domain or one of its subdomains.
+    pub fn with_domain_suffix_match(mut self, domain: impl Into<String>) -> Self {
+        self.domain_suffix_match = Some(domain.into());
+        self
+    }
+
+    /// Set the password to decrypt the private key.
+    pub fn with_private_key_password(mut self, password: impl Into<String>) -> Self {
+        self.private_key_password = Some(password.into());
+        self
+    }
+
+    /// Set how the password is stored.
+    pub fn with_password_flags(mut self, flags: SecretFlags) -> Self {
+        self.password_flags = Some(flags);
+        self
+    }
+
+    /// Check that the settings are consistent.
+    ///
+    /// This checks that an EAP method is set and that certificate paths are absolute, as
+    /// NetworkManager requires.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.eap.as_deref().unwrap_or_default().is_empty() && !self.extra.contains_key("eap") {
+            return Err(Error::InvalidSetting(format!(
+                "{}.eap must have at least one method",
+                Self::NAME
+            )));
+        }
+
+        for (key, cert) in [
+            ("ca-cert", &self.ca_cert),
+            ("client-cert", &self.client_cert),
+            ("private-key", &self.private_key),
+            ("phase2-ca-cert", &self.phase2_ca_cert),
+            ("phase2-client-cert", &self.phase2_client_cert),
+            ("phase2-private-key", &self.phase2_private_key),
+        ] {
+            if let Some(Certificate::Path(path)) = cert {
+                if !path.is_absolute() {
+                    return Err(Error::InvalidSetting(format!(
+                        "{}.{key} must be an absolute path",
+                        Self::NAME
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+string_enum! {
+    /// An EAP method for 802.1X authentication.
+    pub enum EapMethod {
+        /// EAP-TLS, with a client certificate.
+        Tls = "tls",
+
+        /// Protected EAP, with an inner method in a TLS tunnel.
+        Peap = "peap",
+
+        /// Tunneled TLS, with an inner method in a TLS tunnel.
+        Ttls = "ttls",
+
+        /// EAP-pwd, with a password.
+        Pwd = "pwd",
+
+        /// EAP-FAST, with a protected access credential.
+        Fast = "fast",
+
+        /// Lightweight EAP.
+        Leap = "leap",
+
+        /// EAP-MD5.
+        Md5 = "md5",
+
+        /// EAP-SIM, with a GSM SIM card.
+        Sim = "sim",
+
+        /// EAP-AKA, with a UMTS SIM card.
+        Aka = "aka",
+
+        /// EAP-AKA', with a UMTS SIM card.
+        AkaPrime = "aka'",
+    }
+}
+
+impl SettingValue for Vec<EapMethod> {
+    const SIGNATURE: &'static Signature = <Vec<String> as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        Vec::<String>::from_value(value)?
+            .iter()
+            .map(|method| method.parse().ok())
+            .collect()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        self.iter()
+            .map(|method| method.as_str().to_owned())
+            .collect::<Vec<_>>()
+            .to_value()
+    }
+}
+
+string_enum! {
+    /// An authentication method used inside the tunnel of PEAP or TTLS.
+    pub enum Phase2Auth {
+        /// Password Authentication Protocol, TTLS only.
+        Pap = "pap",
+
+        /// Challenge-Handshake Authentication Protocol, TTLS only.
+        Chap = "chap",
+
+        /// Microsoft CHAP, TTLS only.
+        Mschap = "mschap",
+
+        /// Microsoft CHAP version 2.
+        Mschapv2 = "mschapv2",
+
+        /// Generic Token Card.
+        Gtc = "gtc",
+
+        /// One-Time Password.
+        Otp = "otp",
+
+        /// MD5 challenge.
+        Md5 = "md5",
+
+        /// TLS with a client certificate.
+        Tls = "tls",
+    }
+}
+
+/// A certificate or private key for 802.1X authentication.
+///
+/// NetworkManager stores these as byte arrays: either the certificate data itself, or a
+/// NUL-terminated `file://` or `pkcs11:` URI referencing it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Certificate {
+    /// A file containing the certificate or key, in PEM, DER or PKCS#12 format.
+    ///
+    /// The path must be absolute.
+    Path(PathBuf),
+
+    /// A PKCS#11 URI, like `pkcs11:token=Smartcard;object=MyCert`, referencing an object on a
+    /// token.
+    Pkcs11(String),
+
+    /// The certificate data itself.
+    Blob(Vec<u8>),
+}
+
+impl Certificate {
+    const PATH_PREFIX: &'static [u8] = b"file://";
+    const PKCS11_PREFIX: &'static [u8] = b"pkcs11:";
+
+    /// Reference a certificate by path.
+    pub fn path(path: impl AsRef<Path>) -> Self {
+        Certificate::Path(path.as_ref().to_owned())
+    }
+
+    /// Decode a certificate from the byte array form NetworkManager uses.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        let uri = bytes.strip_suffix(b"\0");
+        if let Some(path) = uri.and_then(|uri| uri.strip_prefix(Self::PATH_PREFIX)) {
+            return Certificate::Path(OsStr::from_bytes(path).into());
+        }
+
+        if let Some(uri) = uri.filter(|uri| uri.starts_with(Self::PKCS11_PREFIX)) {
+            if let Ok(uri) = std::str::from_utf8(uri) {
+                return Certificate::Pkcs11(uri.into());
+            }
+        }
+
+        Certificate::Blob(bytes)
+    }
+
+    /// Encode the certificate to the byte array form NetworkManager uses.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Certificate::Path(path) => {
+                let mut bytes = Self::PATH_PREFIX.to_vec();
+                bytes.extend_from_slice(path.as_os_str().as_bytes());
+                bytes.push(0);
+                bytes
+            }
+            Certificate::Pkcs11(uri) => {
+                let mut bytes = uri.as_bytes().to_vec();
+                bytes.push(0);
+                bytes
+            }
+            Certificate::Blob(bytes) => bytes.clone(),
+        }
+    }
+}
+
+impl SettingValue for Certificate {
+    const SIGNATURE: &'static Signature = <Vec<u8> as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        Vec::<u8>::from_value(value).map(Certificate::from_bytes)
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        self.to_bytes().to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path() {
+        let cert = Certificate::path("/etc/ssl/ca.pem");
+        assert_eq!(cert.to_bytes(), b"file:///etc/ssl/ca.pem\0");
+        assert_eq!(Certificate::from_bytes(cert.to_bytes()), cert);
+    }
+
+    #[test]
+    fn pkcs11() {
+        let cert = Certificate::Pkcs11("pkcs11:token=Smartcard;object=MyCert".into());
+        assert_eq!(cert.to_bytes(), b"pkcs11:token=Smartcard;object=MyCert\0");
+        assert_eq!(Certificate::from_bytes(cert.to_bytes()), cert);
+    }
+
+    #[test]
+    fn blob() {
+        let pem = b"-----BEGIN CERTIFICATE-----\n".to_vec();
+        assert_eq!(
+            Certificate::from_bytes(pem.clone()),
+            Certificate::Blob(pem.clone())
+        );
+        assert_eq!(Certificate::Blob(pem.clone()).to_bytes(), pem);
+
+        // URIs without the NUL terminator are data
+        let bytes = b"file:///etc/ssl/ca.pem".to_vec();
+        assert_eq!(
+            Certificate::from_bytes(bytes.clone()),
+            Certificate::Blob(bytes)
+        );
+    }
+
+    #[test]
+    fn validate() {
+        let setting = Ieee8021xSetting {
+            eap: Some(vec![EapMethod::Tls]),
+            ca_cert: Some(Certificate::path("ca.pem")),
+            ..Default::default()
+        };
+        assert!(setting.validate().is_err());
+
+        let setting = Ieee8021xSetting {
+            ca_cert: Some(Certificate::path("/etc/ssl/ca.pem")),
+            ..setting
+        };
+        assert!(setting.validate().is_ok());
+        assert!(Ieee8021xSetting::default().validate().is_err());
+    }
+}
@@ -0,0 +1,610 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    net::{Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use zbus::zvariant::{OwnedValue, Signature, Type, Value};
+
+use crate::Error;
+
+use super::{clone_properties, owned_value, SettingValue};
+
+/// An IP address family, used to share the address and route types between IPv4 and IPv6.
+pub(crate) trait IpFamily: Copy + Display + FromStr {
+    /// The `AF_*` constant for the family.
+    const FAMILY: i32;
+
+    /// The longest prefix length, that of a single address.
+    const MAX_PREFIX: u8;
+
+    /// The prefix length keyfiles default to for addresses written without one.
+    const DEFAULT_PREFIX: u8;
+
+    /// Whether this is the unspecified address, eg `0.0.0.0`.
+    fn is_unspecified(&self) -> bool;
+}
+
+impl IpFamily for Ipv4Addr {
+    const FAMILY: i32 = 2;
+    const MAX_PREFIX: u8 = 32;
+    const DEFAULT_PREFIX: u8 = 24;
+
+    fn is_unspecified(&self) -> bool {
+        Ipv4Addr::is_unspecified(self)
+    }
+}
+
+impl IpFamily for Ipv6Addr {
+    const FAMILY: i32 = 10;
+    const MAX_PREFIX: u8 = 128;
+    const DEFAULT_PREFIX: u8 = 64;
+
+    fn is_unspecified(&self) -> bool {
+        Ipv6Addr::is_unspecified(self)
+    }
+}
+
+/// A statically configured IP address with its prefix length.
+///
+/// Serialised to the `address-data` property.
+#[derive(Debug, PartialEq)]
+pub struct IpAddress<A> {
+    /// The address.
+    pub address: A,
+
+    /// The prefix length, eg 24 for `/24`.
+    ///
+    /// This is at most 32 for IPv4 and 128 for IPv6.
+    pub prefix: u8,
+
+    /// Attributes not modelled by this crate, kept as-is.
+    pub attributes: HashMap<String, OwnedValue>,
+}
+
+impl<A> IpAddress<A> {
+    /// Create an address with the given prefix length.
+    pub fn new(address: A, prefix: u8) -> Self {
+        Self {
+            address,
+            prefix,
+            attributes: HashMap::new(),
+        }
+    }
+}
+
+/// A statically configured IP route.
+///
+/// Serialised to the `route-data` property.
+#[derive(Debug, PartialEq)]
+pub struct IpRoute<A> {
+    /// The destination network.
+    pub dest: A,
+
+    /// The prefix length of the destination network, eg 0 for a default route.
+    ///
+    /// This is at most 32 for IPv4 and 128 for IPv6.
+    pub prefix: u8,
+
+    /// The gateway to reach the destination through.
+    ///
+    /// If None, the destination is directly reachable on the link.
+    pub next_hop: Option<A>,
+
+    /// The route metric.
+    ///
+    /// If None, the `route-metric` of the setting is used.
+    pub metric: Option<u32>,
+
+    /// The routing table to add the route to.
+    ///
+    /// If None, the `route-table` of the setting is used.
+    pub table: Option<u32>,
+
+    /// Other route attributes, like `src`, `mtu` or `onlink`, kept as-is.
+    ///
+    /// See the `routes` property in the setting documentation for the list of attributes.
+    pub attributes: HashMap<String, OwnedValue>,
+}
+
+impl<A> IpRoute<A> {
+    /// Create a route to the destination network, reachable on the link.
+    pub fn new(dest: A, prefix: u8) -> Self {
+        Self {
+            dest,
+            prefix,
+            next_hop: None,
+            metric: None,
+            table: None,
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Route via the given gateway.
+    pub fn via(mut self, next_hop: A) -> Self {
+        self.next_hop = Some(next_hop);
+        self
+    }
+
+    /// Set the route metric.
+    pub fn metric(mut self, metric: u32) -> Self {
+        self.metric = Some(metric);
+        self
+    }
+
+    /// Add the route to the given routing table.
+    pub fn table(mut self, table: u32) -> Self {
+        self.table = Some(table);
+        self
+    }
+}
+
+/// A policy routing rule.
+///
+/// Serialised to the `routing-rules` property. The address family is that of the setting the rule
+/// is in.
+#[derive(Debug, PartialEq)]
+pub struct RoutingRule<A> {
+    /// The priority of the rule. Rules are evaluated in increasing priority order.
+    pub priority: Option<u32>,
+
+    /// Match packets from this source network, with its prefix length.
+    pub from: Option<(A, u8)>,
+
+    /// Match packets to this destination network, with its prefix length.
+    pub to: Option<(A, u8)>,
+
+    /// Match packets with this firewall mark.
+    pub fwmark: Option<u32>,
+
+    /// The mask applied to the firewall mark before matching.
+    pub fwmask: Option<u32>,
+
+    /// Match packets coming in on this interface.
+    pub iifname: Option<String>,
+
+    /// Match packets going out on this interface.
+    pub oifname: Option<String>,
+
+    /// Invert the match.
+    pub invert: Option<bool>,
+
+    /// Look up the route in this table if the rule matches.
+    pub table: Option<u32>,
+
+    /// Other rule attributes, like `action` or `suppress-prefixlength`, kept as-is.
+    pub attributes: HashMap<String, OwnedValue>,
+}
+
+impl<A> Default for RoutingRule<A> {
+    fn default() -> Self {
+        Self {
+            priority: None,
+            from: None,
+            to: None,
+            fwmark: None,
+            fwmask: None,
+            iifname: None,
+            oifname: None,
+            invert: None,
+            table: None,
+            attributes: HashMap::new(),
+        }
+    }
+}
+
+/// Check that the prefix lengths of the addresses, routes and routing rules of an IP setting are
+/// valid for its address family.
+pub(super) fn validate_prefixes<A: IpFamily>(
+    setting: &str,
+    addresses: Option<&[IpAddress<A>]>,
+    routes: Option<&[IpRoute<A>]>,
+    rules: Option<&[RoutingRule<A>]>,
+) -> Result<(), Error> {
+    let addresses = addresses
+        .into_iter()
+        .flatten()
+        .map(|address| ("address-data", address.prefix));
+    let routes = routes
+        .into_iter()
+        .flatten()
+        .map(|route| ("route-data", route.prefix));
+    let rules = rules.into_iter().flatten().flat_map(|rule| {
+        [rule.from, rule.to]
+            .into_iter()
+            .flatten()
+            .map(|(_, len)| ("routing-rules", len))
+    });
+    match addresses
+        .chain(routes)
+        .chain(rules)
+        .find(|(_, prefix)| *prefix > A::MAX_PREFIX)
+    {
+        Some((key, prefix)) => Err(Error::InvalidSetting(format!(
+            "{setting}.{key} has a prefix length of {prefix}, longer than {}",
+            A::MAX_PREFIX
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub(super) type Entry = HashMap<String, OwnedValue>;
+
+pub(super) fn entries(value: OwnedValue) -> Option<Vec<Entry>> {
+    Vec::<Entry>::try_from(value).ok()
+}
+
+pub(super) fn entries_value(entries: Vec<Entry>) -> Result<OwnedValue, Error> {
+    owned_value(Value::from(entries))
+}
+
+pub(super) fn take<T: SettingValue>(entry: &mut Entry, key: &str) -> Option<Option<T>> {
+    match entry.remove(key) {
+        Some(value) => T::from_value(value).map(Some),
+        None => Some(None),
+    }
+}
+
+fn take_addr<A: IpFamily>(entry: &mut Entry, key: &str) -> Option<Option<A>> {
+    match take::<String>(entry, key)? {
+        Some(addr) => addr.parse().ok().map(Some),
+        None => Some(None),
+    }
+}
+
+pub(super) fn put<T: SettingValue>(
+    entry: &mut Entry,
+    key: &str,
+    value: &Option<T>,
+) -> Result<(), Error> {
+    if let Some(value) = value {
+        entry.insert(key.into(), value.to_value()?);
+    }
+    Ok(())
+}
+
+fn put_addr<A: IpFamily>(entry: &mut Entry, key: &str, value: &Option<A>) -> Result<(), Error> {
+    put(entry, key, &value.map(|addr| addr.to_string()))
+}
+
+impl<A: IpFamily> SettingValue for Vec<IpAddress<A>> {
+    const SIGNATURE: &'static Signature = <Vec<Entry> as Type>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        entries(value)?
+            .into_iter()
+            .map(|mut entry| {
+                Some(IpAddress {
+                    address: take_addr(&mut entry, "address")??,
+                    prefix: u8::try_from(take::<u32>(&mut entry, "prefix")??).ok()?,
+                    attributes: entry,
+                })
+            })
+            .collect()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        entries_value(
+            self.iter()
+                .map(|address| {
+                    let mut entry = clone_properties(&address.attributes)?;
+                    put_addr(&mut entry, "address", &Some(address.address))?;
+                    put(&mut entry, "prefix", &Some(u32::from(address.prefix)))?;
+                    Ok(entry)
+                })
+                .collect::<Result<_, Error>>()?,
+        )
+    }
+}
+
+impl<A: IpFamily> SettingValue for Vec<IpRoute<A>> {
+    const SIGNATURE: &'static Signature = <Vec<Entry> as Type>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        entries(value)?
+            .into_iter()
+            .map(|mut entry| {
+                Some(IpRoute {
+                    dest: take_addr(&mut entry, "dest")??,
+                    prefix: u8::try_from(take::<u32>(&mut entry, "prefix")??).ok()?,
+                    next_hop: take_addr(&mut entry, "next-hop")?,
+                    metric: take(&mut entry, "metric")?,
+                    table: take(&mut entry, "table")?,
+                    attributes: entry,
+                })
+            })
+            .collect()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        entries_value(
+            self.iter()
+                .map(|route| {
+                    let mut entry = clone_properties(&route.attributes)?;
+                    put_addr(&mut entry, "dest", &Some(route.dest))?;
+                    put(&mut entry, "prefix", &Some(u32::from(route.prefix)))?;
+                    put_addr(&mut entry, "next-hop", &route.next_hop)?;
+                    put(&mut entry, "metric", &route.metric)?;
+                    put(&mut entry, "table", &route.table)?;
+                    Ok(entry)
+                })
+                .collect::<Result<_, Error>>()?,
+        )
+    }
+}
+
+impl<A: IpFamily> SettingValue for Vec<RoutingRule<A>> {
+    const SIGNATURE: &'static Signature = <Vec<Entry> as Type>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        fn take_network<A: IpFamily>(
+            entry: &mut Entry,
+            key: &str,
+            len_key: &str,
+        ) -> Option<Option<(A, u8)>> {
+            let addr = take_addr(entry, key)?;
+            let len = match entry.remove(len_key) {
+                Some(len) => Some(u8::try_from(len).ok()?),
+                None => None,
+            };
+            Some(addr.map(|addr| (addr, len.unwrap_or(0))))
+        }
+
+        entries(value)?
+            .into_iter()
+            .map(|mut entry| {
+                if take::<i32>(&mut entry, "family")?? != A::FAMILY {
+                    return None;
+                }
+
+                Some(RoutingRule {
+                    priority: take(&mut entry, "priority")?,
+                    from: take_network(&mut entry, "from", "from-len")?,
+                    to: take_network(&mut entry, "to", "to-len")?,
+                    fwmark: take(&mut entry, "fwmark")?,
+                    fwmask: take(&mut entry, "fwmask")?,
+                    iifname: take(&mut entry, "iifname")?,
+                    oifname: take(&mut entry, "oifname")?,
+                    invert: take(&mut entry, "invert")?,
+                    table: take(&mut entry, "table")?,
+                    attributes: entry,
+                })
+            })
+            .collect()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        fn put_network<A: IpFamily>(
+            entry: &mut Entry,
+            key: &str,
+            len_key: &str,
+            network: &Option<(A, u8)>,
+        ) -> Result<(), Error> {
+            if let Some((addr, len)) = network {
+                put_addr(entry, key, &Some(*addr))?;
+                entry.insert(len_key.into(), OwnedValue::from(*len));
+            }
+            Ok(())
+        }
+
+        entries_value(
+            self.iter()
+                .map(|rule| {
+                    let mut entry = clone_properties(&rule.attributes)?;
+                    put(&mut entry, "family", &Some(A::FAMILY))?;
+                    put(&mut entry, "priority", &rule.priority)?;
+                    put_network(&mut entry, "from", "from-len", &rule.from)?;
+                    put_network(&mut entry, "to", "to-len", &rule.to)?;
+                    put(&mut entry, "fwmark", &rule.fwmark)?;
+                    put(&mut entry, "fwmask", &rule.fwmask)?;
+                    put(&mut entry, "iifname", &rule.iifname)?;
+                    put(&mut entry, "oifname", &rule.oifname)?;
+                    put(&mut entry, "invert", &rule.invert)?;
+                    put(&mut entry, "table", &rule.table)?;
+                    Ok(entry)
+                })
+                .collect::<Result<_, Error>>()?,
+        )
+    }
+}
+
+impl SettingValue for Ipv4Addr {
+    const SIGNATURE: &'static Signature = <String as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        String::try_from(value).ok()?.parse().ok()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        self.to_string().to_value()
+    }
+}
+
+impl SettingValue for Ipv6Addr {
+    const SIGNATURE: &'static Signature = <String as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        String::try_from(value).ok()?.parse().ok()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        self.to_string().to_value()
+    }
+}
+
+/// IPv4 DNS servers are sent as `au`, with each address in network byte order.
+impl SettingValue for Vec<Ipv4Addr> {
+    const SIGNATURE: &'static Signature = <Vec<u32> as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        Some(
+            Vec::<u32>::from_value(value)?
+                .into_iter()
+                .map(|addr| Ipv4Addr::from(addr.to_ne_bytes()))
+                .collect(),
+        )
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        self.iter()
+            .map(|addr| u32::from_ne_bytes(addr.octets()))
+            .collect::<Vec<_>>()
+            .to_value()
+    }
+}
+
+/// IPv6 DNS servers are sent as `aay`, with each address as its 16 octets.
+impl SettingValue for Vec<Ipv6Addr> {
+    const SIGNATURE: &'static Signature = <Vec<Vec<u8>> as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        Vec::<Vec<u8>>::from_value(value)?
+            .into_iter()
+            .map(|addr| Some(Ipv6Addr::from(<[u8; 16]>::try_from(addr).ok()?)))
+            .collect()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        self.iter()
+            .map(|addr| addr.octets().to_vec())
+            .collect::<Vec<_>>()
+            .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setting::{clone_value, Ipv4Setting, Ipv6Setting};
+
+    #[test]
+    fn ipv4_dns_is_network_order_au() {
+        let dns = vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(8, 8, 4, 4)];
+        let value = dns.to_value().unwrap();
+        assert_eq!(value.value_signature(), "au");
+        assert_eq!(
+            Vec::<u32>::from_value(clone_value(&value).unwrap()).unwrap(),
+            [
+                u32::from_ne_bytes([192, 168, 1, 1]),
+                u32::from_ne_bytes([8, 8, 4, 4])
+            ]
+        );
+        assert_eq!(Vec::<Ipv4Addr>::from_value(value).unwrap(), dns);
+    }
+
+    #[test]
+    fn ipv6_dns_is_aay() {
+        let dns = vec!["2001:4860:4860::8888".parse::<Ipv6Addr>().unwrap()];
+        let value = dns.to_value().unwrap();
+        assert_eq!(value.value_signature(), "aay");
+        assert_eq!(
+            Vec::<Vec<u8>>::from_value(clone_value(&value).unwrap()).unwrap(),
+            [dns[0].octets().to_vec()]
+        );
+        assert_eq!(Vec::<Ipv6Addr>::from_value(value).unwrap(), dns);
+    }
+
+    #[test]
+    fn address_data() {
+        let addresses = vec![IpAddress::new(Ipv4Addr::new(10, 0, 0, 2), 24)];
+        let value = addresses.to_value().unwrap();
+        assert_eq!(value.value_signature(), "aa{sv}");
+
+        let entries = entries(clone_value(&value).unwrap()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            String::from_value(clone_value(&entries[0]["address"]).unwrap()).unwrap(),
+            "10.0.0.2"
+        );
+        assert_eq!(
+            u32::from_value(clone_value(&entries[0]["prefix"]).unwrap()),
+            Some(24)
+        );
+        assert_eq!(
+            Vec::<IpAddress<Ipv4Addr>>::from_value(value).unwrap(),
+            addresses
+        );
+    }
+
+    #[test]
+    fn route_data() {
+        let routes = vec![
+            IpRoute::new("2001:db8::".parse::<Ipv6Addr>().unwrap(), 32)
+                .via("fe80::1".parse().unwrap())
+                .metric(100)
+                .table(200),
+            IpRoute::new("2001:db8:1::".parse().unwrap(), 48),
+        ];
+        let value = routes.to_value().unwrap();
+        assert_eq!(value.value_signature(), "aa{sv}");
+
+        let entries = entries(clone_value(&value).unwrap()).unwrap();
+        assert_eq!(
+            String::from_value(clone_value(&entries[0]["next-hop"]).unwrap()).unwrap(),
+            "fe80::1"
+        );
+        assert_eq!(
+            u32::from_value(clone_value(&entries[0]["metric"]).unwrap()),
+            Some(100)
+        );
+        assert!(!entries[1].contains_key("next-hop"));
+        assert_eq!(Vec::<IpRoute<Ipv6Addr>>::from_value(value).unwrap(), routes);
+    }
+
+    #[test]
+    fn routing_rule_lengths_are_bytes() {
+        let rules = vec![RoutingRule {
+            priority: Some(5),
+            from: Some((Ipv4Addr::new(10, 0, 0, 0), 8)),
+            table: Some(100),
+            ..Default::default()
+        }];
+        let value = rules.to_value().unwrap();
+
+        let entries = entries(clone_value(&value).unwrap()).unwrap();
+        assert_eq!(entries[0]["family"], 2i32.to_value().unwrap());
+        assert_eq!(entries[0]["from-len"].value_signature(), "y");
+        assert_eq!(
+            u8::try_from(clone_value(&entries[0]["from-len"]).unwrap()),
+            Ok(8)
+        );
+        assert_eq!(
+            Vec::<RoutingRule<Ipv4Addr>>::from_value(value).unwrap(),
+            rules
+        );
+
+        // rules of the other family are rejected
+        assert!(Vec::<RoutingRule<Ipv6Addr>>::from_value(rules.to_value().unwrap()).is_none());
+    }
+
+    #[test]
+    fn prefix_lengths_are_validated() {
+        let address = Ipv4Addr::new(10, 0, 0, 2);
+        assert!(Ipv4Setting::default()
+            .with_address(address, 32)
+            .validate()
+            .is_ok());
+        assert!(Ipv4Setting::default()
+            .with_address(address, 33)
+            .validate()
+            .is_err());
+        assert!(Ipv4Setting::default()
+            .with_route(IpRoute::new(address, 64))
+            .validate()
+            .is_err());
+
+        let address = Ipv6Addr::LOCALHOST;
+        assert!(Ipv6Setting::default()
+            .with_address(address, 128)
+            .validate()
+            .is_ok());
+        assert!(Ipv6Setting::default()
+            .with_routing_rule(RoutingRule {
+                to: Some((address, 129)),
+                ..Default::default()
+            })
+            .validate()
+            .is_err());
+    }
+}
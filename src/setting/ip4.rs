@@ -0,0 +1,145 @@
+use std::net::Ipv4Addr;
+
+use crate::Error;
+
+use super::{
+    ip::validate_prefixes, setting, string_enum, IpAddress, IpRoute, RoutingRule, Setting,
+};
+
+setting! {
+    /// IPv4 settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-ipv4.html)
+    pub struct Ipv4Setting = "ipv4" {
+        /// How IPv4 is configured.
+        method: Ipv4Method = "method",
+
+        /// Statically configured addresses.
+        addresses: Vec<IpAddress<Ipv4Addr>> = "address-data",
+
+        /// The gateway of the statically configured addresses.
+        ///
+        /// This is only meaningful with [`Ipv4Method::Manual`], and requires at least one address.
+        gateway: Ipv4Addr = "gateway",
+
+        /// Statically configured routes.
+        routes: Vec<IpRoute<Ipv4Addr>> = "route-data",
+
+        /// Policy routing rules.
+        routing_rules: Vec<RoutingRule<Ipv4Addr>> = "routing-rules",
+
+        /// DNS servers.
+        dns: Vec<Ipv4Addr> = "dns",
+
+        /// DNS search domains.
+        dns_search: Vec<String> = "dns-search",
+
+        /// DNS resolver options, see `resolv.conf(5)`.
+        dns_options: Vec<String> = "dns-options",
+
+        /// The priority of DNS servers of this connection relative to others.
+        ///
+        /// Lower values have higher priority. Zero means the default.
+        dns_priority: i32 = "dns-priority",
+
+        /// Whether to ignore DNS servers obtained automatically, and only use the ones configured.
+        ignore_auto_dns: bool = "ignore-auto-dns",
+
+        /// Whether to ignore routes obtained automatically, and only use the ones configured.
+        ignore_auto_routes: bool = "ignore-auto-routes",
+
+        /// Whether to never use this connection as the default route.
+        never_default: bool = "never-default",
+
+        /// Whether the connection may complete without IPv4 being configured.
+        may_fail: bool = "may-fail",
+
+        /// The metric of the default route and of routes without an explicit metric.
+        ///
+        /// -1 means the default.
+        route_metric: i64 = "route-metric",
+
+        /// The routing table to use for routes without an explicit table.
+        ///
+        /// Zero means the main table.
+        route_table: u32 = "route-table",
+
+        /// Whether to send a hostname to the DHCP server.
+        dhcp_send_hostname: bool = "dhcp-send-hostname",
+
+        /// The hostname to send to the DHCP server.
+        dhcp_hostname: String = "dhcp-hostname",
+    }
+}
+
+impl Ipv4Setting {
+    /// Create IPv4 settings with the given method.
+    pub fn new(method: Ipv4Method) -> Self {
+        Self {
+            method: Some(method),
+            ..Default::default()
+        }
+    }
+
+    /// Add a statically configured address.
+    pub fn with_address(mut self, address: Ipv4Addr, prefix: u8) -> Self {
+        self.addresses
+            .get_or_insert_with(Vec::new)
+            .push(IpAddress::new(address, prefix));
+        self
+    }
+
+    /// Set the gateway.
+    pub fn with_gateway(mut self, gateway: Ipv4Addr) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
+
+    /// Add a statically configured route.
+    pub fn with_route(mut self, route: IpRoute<Ipv4Addr>) -> Self {
+        self.routes.get_or_insert_with(Vec::new).push(route);
+        self
+    }
+
+    /// Add a policy routing rule.
+    pub fn with_routing_rule(mut self, rule: RoutingRule<Ipv4Addr>) -> Self {
+        self.routing_rules.get_or_insert_with(Vec::new).push(rule);
+        self
+    }
+
+    /// Add a DNS server.
+    pub fn with_dns(mut self, server: Ipv4Addr) -> Self {
+        self.dns.get_or_insert_with(Vec::new).push(server);
+        self
+    }
+
+    /// Check that the prefix lengths of addresses, routes and routing rules are at most 32.
+    pub fn validate(&self) -> Result<(), Error> {
+        validate_prefixes(
+            Self::NAME,
+            self.addresses.as_deref(),
+            self.routes.as_deref(),
+            self.routing_rules.as_deref(),
+        )
+    }
+}
+
+string_enum! {
+    /// How IPv4 is configured on a connection.
+    pub enum Ipv4Method {
+        /// Use DHCP.
+        Auto = "auto",
+
+        /// Use the statically configured addresses.
+        Manual = "manual",
+
+        /// Use an IPv4 link-local address (`169.254.0.0/16`).
+        LinkLocal = "link-local",
+
+        /// Share the connection with other computers, using NAT and a DHCP server.
+        Shared = "shared",
+
+        /// Disable IPv4.
+        Disabled = "disabled",
+    }
+}
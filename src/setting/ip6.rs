@@ -0,0 +1,165 @@
+use std::net::Ipv6Addr;
+
+use crate::Error;
+
+use super::{
+    ip::validate_prefixes, setting, string_enum, IpAddress, IpRoute, RoutingRule, Setting,
+};
+
+setting! {
+    /// IPv6 settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-ipv6.html)
+    pub struct Ipv6Setting = "ipv6" {
+        /// How IPv6 is configured.
+        method: Ipv6Method = "method",
+
+        /// Statically configured addresses.
+        addresses: Vec<IpAddress<Ipv6Addr>> = "address-data",
+
+        /// The gateway of the statically configured addresses.
+        ///
+        /// This is only meaningful with [`Ipv6Method::Manual`], and requires at least one address.
+        gateway: Ipv6Addr = "gateway",
+
+        /// Statically configured routes.
+        routes: Vec<IpRoute<Ipv6Addr>> = "route-data",
+
+        /// Policy routing rules.
+        routing_rules: Vec<RoutingRule<Ipv6Addr>> = "routing-rules",
+
+        /// DNS servers.
+        dns: Vec<Ipv6Addr> = "dns",
+
+        /// DNS search domains.
+        dns_search: Vec<String> = "dns-search",
+
+        /// DNS resolver options, see `resolv.conf(5)`.
+        dns_options: Vec<String> = "dns-options",
+
+        /// The priority of DNS servers of this connection relative to others.
+        ///
+        /// Lower values have higher priority. Zero means the default.
+        dns_priority: i32 = "dns-priority",
+
+        /// Whether to ignore DNS servers obtained automatically, and only use the ones configured.
+        ignore_auto_dns: bool = "ignore-auto-dns",
+
+        /// Whether to ignore routes obtained automatically, and only use the ones configured.
+        ignore_auto_routes: bool = "ignore-auto-routes",
+
+        /// Whether to never use this connection as the default route.
+        never_default: bool = "never-default",
+
+        /// Whether the connection may complete without IPv6 being configured.
+        may_fail: bool = "may-fail",
+
+        /// The metric of the default route and of routes without an explicit metric.
+        ///
+        /// -1 means the default.
+        route_metric: i64 = "route-metric",
+
+        /// The routing table to use for routes without an explicit table.
+        ///
+        /// Zero means the main table.
+        route_table: u32 = "route-table",
+
+        /// Whether to send a hostname to the DHCP server.
+        dhcp_send_hostname: bool = "dhcp-send-hostname",
+
+        /// The hostname to send to the DHCP server.
+        dhcp_hostname: String = "dhcp-hostname",
+
+        /// How to generate the interface identifier of SLAAC addresses.
+        ///
+        /// 0 means EUI-64, 1 means stable-privacy (RFC 7217).
+        addr_gen_mode: i32 = "addr-gen-mode",
+
+        /// Whether to use IPv6 privacy extensions (RFC 4941).
+        ///
+        /// -1 means the default, 0 disabled, 1 enabled preferring public addresses, and 2 enabled
+        /// preferring temporary addresses.
+        ip6_privacy: i32 = "ip6-privacy",
+
+        /// The interface identifier to use for SLAAC addresses, like `::1:2:3:4`.
+        token: String = "token",
+    }
+}
+
+impl Ipv6Setting {
+    /// Create IPv6 settings with the given method.
+    pub fn new(method: Ipv6Method) -> Self {
+        Self {
+            method: Some(method),
+            ..Default::default()
+        }
+    }
+
+    /// Add a statically configured address.
+    pub fn with_address(mut self, address: Ipv6Addr, prefix: u8) -> Self {
+        self.addresses
+            .get_or_insert_with(Vec::new)
+            .push(IpAddress::new(address, prefix));
+        self
+    }
+
+    /// Set the gateway.
+    pub fn with_gateway(mut self, gateway: Ipv6Addr) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
+
+    /// Add a statically configured route.
+    pub fn with_route(mut self, route: IpRoute<Ipv6Addr>) -> Self {
+        self.routes.get_or_insert_with(Vec::new).push(route);
+        self
+    }
+
+    /// Add a policy routing rule.
+    pub fn with_routing_rule(mut self, rule: RoutingRule<Ipv6Addr>) -> Self {
+        self.routing_rules.get_or_insert_with(Vec::new).push(rule);
+        self
+    }
+
+    /// Add a DNS server.
+    pub fn with_dns(mut self, server: Ipv6Addr) -> Self {
+        self.dns.get_or_insert_with(Vec::new).push(server);
+        self
+    }
+
+    /// Check that the prefix lengths of addresses, routes and routing rules are at most 128.
+    pub fn validate(&self) -> Result<(), Error> {
+        validate_prefixes(
+            Self::NAME,
+            self.addresses.as_deref(),
+            self.routes.as_deref(),
+            self.routing_rules.as_deref(),
+        )
+    }
+}
+
+string_enum! {
+    /// How IPv6 is configured on a connection.
+    pub enum Ipv6Method {
+        /// Use SLAAC, and DHCPv6 if the router advertisements ask for it.
+        Auto = "auto",
+
+        /// Use DHCPv6 only.
+        Dhcp = "dhcp",
+
+        /// Use the statically configured addresses.
+        Manual = "manual",
+
+        /// Only configure a link-local address.
+        LinkLocal = "link-local",
+
+        /// Share the connection with other computers, using prefix delegation.
+        Shared = "shared",
+
+        /// Don't touch the IPv6 configuration of the device.
+        Ignore = "ignore",
+
+        /// Disable IPv6.
+        Disabled = "disabled",
+    }
+}
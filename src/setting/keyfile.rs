@@ -0,0 +1,1576 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use zbus::zvariant::OwnedValue;
+
+use crate::Error;
+
+use super::{
+    clone_value,
+    ip::{entries, entries_value, Entry, IpFamily},
+    BondSetting, Certificate, ConnectionSetting, ConnectionSettings, Ieee8021xSetting, Ipv4Setting,
+    Ipv6Setting, RawSettings, Setting, SettingValue, WireGuardSetting, WirelessSetting,
+};
+
+/// Settings whose keyfile group has a shorter name.
+const ALIASES: &[(&str, &str)] = &[
+    ("802-3-ethernet", "ethernet"),
+    ("802-11-wireless", "wifi"),
+    ("802-11-wireless-security", "wifi-security"),
+];
+
+/// The keyfile key of the `assigned-mac-address` property, which also takes a MAC address.
+const CLONED_MAC_ADDRESS: &str = "cloned-mac-address";
+
+/// Properties holding MAC addresses as bytes, which keyfiles write as `AA:BB:CC:DD:EE:FF`.
+const MAC_ADDRESSES: &[&str] = &["mac-address", CLONED_MAC_ADDRESS, "bssid"];
+
+/// The special values of `assigned-mac-address`.
+const MAC_ADDRESS_MODES: &[&str] = &["preserve", "permanent", "random", "stable", "stable-ssid"];
+
+/// Properties of the `802-1x` setting holding [`Certificate`]s.
+const CERTIFICATES: &[&str] = &[
+    "ca-cert",
+    "client-cert",
+    "private-key",
+    "phase2-ca-cert",
+    "phase2-client-cert",
+    "phase2-private-key",
+];
+
+/// The names of the `ipv6.addr-gen-mode` values, by value.
+const ADDR_GEN_MODES: &[&str] = &["eui64", "stable-privacy", "default-or-eui64", "default"];
+
+const VPN: &str = "vpn";
+const VPN_SECRETS: &str = "vpn-secrets";
+const WIREGUARD_PEER: &str = "wireguard-peer.";
+
+impl ConnectionSettings {
+    /// Parse a NetworkManager keyfile, the `.nmconnection` files profiles are stored in.
+    ///
+    /// This reads the same settings [`Connection::settings()`] returns for the profile, plus the
+    /// secrets stored in the file, without a running NetworkManager. It supports the keyfile
+    /// encodings of addresses (`address1=10.0.0.2/24,10.0.0.1`), routes (`route1=` and
+    /// `route1_options=`), routing rules, DNS servers, MAC addresses, SSIDs, certificates, bond
+    /// options, VPN data and WireGuard peers.
+    ///
+    /// Properties are typed by the settings this crate models. The properties it doesn't model are
+    /// read as strings, which round-trip through [`ConnectionSettings::to_keyfile()`] unchanged,
+    /// but which NetworkManager rejects over D-Bus unless they are strings there too.
+    ///
+    /// [`Connection::settings()`]: crate::connection::Connection::settings()
+    pub fn from_keyfile(keyfile: &str) -> Result<Self, Error> {
+        Self::from_dbus(read(keyfile)?)
+    }
+
+    /// Write these settings as a NetworkManager keyfile.
+    ///
+    /// The result can be placed in `/etc/NetworkManager/system-connections`, with a name ending
+    /// in `.nmconnection` and permissions of `0600`, as NetworkManager ignores keyfiles other users
+    /// can read. Secrets that are present in the settings are written to the file.
+    ///
+    /// ```rust,no_run
+    /// # use passcod_networkmanager::{setting::{BondMode, BondSetting, ConnectionSettings}, Error};
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bond = ConnectionSettings::bond("bond0", BondSetting::new(BondMode::ActiveBackup));
+    /// std::fs::write("rootfs/etc/NetworkManager/system-connections/bond0.nmconnection", bond.to_keyfile()?)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn to_keyfile(&self) -> Result<String, Error> {
+        write(&self.to_dbus()?)
+    }
+}
+
+/// A group of a keyfile being written, as its name and lines.
+type Group = (String, Vec<(String, String)>);
+
+/// A `key=value` line of a keyfile.
+struct KeyfileEntry<'a> {
+    line: usize,
+    key: &'a str,
+    value: &'a str,
+}
+
+impl KeyfileEntry<'_> {
+    fn error(&self, message: String) -> Error {
+        Error::Parse {
+            line: self.line,
+            message,
+        }
+    }
+
+    fn invalid(&self, group: &str) -> Error {
+        self.error(format!("invalid value for {group}.{}", self.key))
+    }
+
+    fn string(&self) -> String {
+        unescape(self.value)
+    }
+
+    fn list(&self) -> Vec<String> {
+        unescape_list(self.value)
+    }
+
+    fn parse<T: std::str::FromStr>(&self, group: &str) -> Result<T, Error> {
+        self.value.parse().map_err(|_| self.invalid(group))
+    }
+
+    fn parse_list<T: std::str::FromStr>(&self, group: &str) -> Result<Vec<T>, Error> {
+        self.list()
+            .iter()
+            .map(|item| item.parse().map_err(|_| self.invalid(group)))
+            .collect()
+    }
+}
+
+/// Split a keyfile into its groups, merging groups that appear more than once.
+fn parse_groups(keyfile: &str) -> Result<Vec<(&str, Vec<KeyfileEntry<'_>>)>, Error> {
+    let mut groups: Vec<(&str, Vec<KeyfileEntry<'_>>)> = Vec::new();
+    let mut current = None;
+    for (index, line) in keyfile.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line
+            .trim_end()
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            current = Some(match groups.iter().position(|(group, _)| *group == name) {
+                Some(position) => position,
+                None => {
+                    groups.push((name, Vec::new()));
+                    groups.len() - 1
+                }
+            });
+            continue;
+        }
+
+        let error = |message: &str| Error::Parse {
+            line: line_number,
+            message: message.into(),
+        };
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key=value` or a `[group]`"))?;
+        let group = current.ok_or_else(|| error("expected a `[group]` first"))?;
+        groups[group].1.push(KeyfileEntry {
+            line: line_number,
+            key: key.trim_end(),
+            value: value.trim_start().trim_end_matches('\r'),
+        });
+    }
+    Ok(groups)
+}
+
+fn read(keyfile: &str) -> Result<RawSettings, Error> {
+    let mut raw = RawSettings::new();
+    let mut peers = Vec::new();
+    for (group, group_entries) in parse_groups(keyfile)? {
+        if let Some(public_key) = group.strip_prefix(WIREGUARD_PEER) {
+            peers.push(read_peer(group, public_key, &group_entries)?);
+            continue;
+        }
+
+        if group == VPN_SECRETS {
+            let secrets = group_entries
+                .iter()
+                .map(|entry| (entry.key.to_owned(), entry.string()))
+                .collect::<HashMap<_, _>>();
+            raw.entry(VPN.into())
+                .or_default()
+                .insert("secrets".into(), secrets.to_value()?);
+            continue;
+        }
+
+        let setting = setting_name(group);
+        let properties = raw.entry(setting.into()).or_default();
+        match setting {
+            Ipv4Setting::NAME => read_ip::<Ipv4Addr>(setting, &group_entries, properties)?,
+            Ipv6Setting::NAME => read_ip::<Ipv6Addr>(setting, &group_entries, properties)?,
+            BondSetting::NAME => {
+                let options = group_entries
+                    .iter()
+                    .filter(|entry| entry.key != "interface-name")
+                    .map(|entry| (entry.key.to_owned(), entry.string()))
+                    .collect::<HashMap<_, _>>();
+                properties.insert("options".into(), options.to_value()?);
+            }
+            VPN => {
+                let mut data = HashMap::new();
+                for entry in &group_entries {
+                    let value = match entry.key {
+                        "service-type" | "user-name" => entry.string().to_value()?,
+                        "persistent" => read_bool(entry, group)?.to_value()?,
+                        "timeout" => entry.parse::<u32>(group)?.to_value()?,
+                        _ => {
+                            data.insert(entry.key.to_owned(), entry.string());
+                            continue;
+                        }
+                    };
+                    properties.insert(entry.key.into(), value);
+                }
+                if !data.is_empty() {
+                    properties.insert("data".into(), data.to_value()?);
+                }
+            }
+            _ => {
+                for entry in &group_entries {
+                    if entry.key == CLONED_MAC_ADDRESS {
+                        properties
+                            .insert("assigned-mac-address".into(), entry.string().to_value()?);
+                        if MAC_ADDRESS_MODES.contains(&entry.value) {
+                            continue;
+                        }
+                    }
+                    properties.insert(entry.key.into(), read_value(setting, entry)?);
+                }
+            }
+        }
+    }
+
+    if !peers.is_empty() {
+        raw.entry(WireGuardSetting::NAME.into())
+            .or_default()
+            .insert("peers".into(), entries_value(peers)?);
+    }
+    Ok(raw)
+}
+
+/// Read a property by its type in the settings this crate models.
+fn read_value(setting: &str, entry: &KeyfileEntry<'_>) -> Result<OwnedValue, Error> {
+    let key = entry.key;
+    if setting == ConnectionSetting::NAME && key == "type" {
+        return setting_name(&entry.string()).to_owned().to_value();
+    }
+    if setting == Ipv6Setting::NAME && key == "addr-gen-mode" {
+        let mode = match ADDR_GEN_MODES.iter().position(|mode| *mode == entry.value) {
+            Some(mode) => mode as i32,
+            None => entry.parse::<i32>(setting)?,
+        };
+        return mode.to_value();
+    }
+    if setting == Ieee8021xSetting::NAME && CERTIFICATES.contains(&key) {
+        let value = entry.string();
+        let certificate = if let Some(data) = value.strip_prefix("data:;base64,") {
+            Certificate::Blob(STANDARD.decode(data).map_err(|_| entry.invalid(setting))?)
+        } else if value.starts_with("pkcs11:") {
+            Certificate::Pkcs11(value)
+        } else {
+            Certificate::path(value.strip_prefix("file://").unwrap_or(&value))
+        };
+        return certificate.to_value();
+    }
+    if MAC_ADDRESSES.contains(&key) {
+        let address = entry
+            .value
+            .split(':')
+            .map(|octet| u8::from_str_radix(octet, 16).ok())
+            .collect::<Option<Vec<_>>>()
+            .or_else(|| read_byte_list(entry.value))
+            .ok_or_else(|| entry.invalid(setting))?;
+        return address.to_value();
+    }
+
+    let Some(signature) = ConnectionSettings::signature(setting, key) else {
+        return entry.string().to_value();
+    };
+    match signature.to_string().as_str() {
+        "b" => read_bool(entry, setting)?.to_value(),
+        "i" => entry.parse::<i32>(setting)?.to_value(),
+        "u" => entry.parse::<u32>(setting)?.to_value(),
+        "x" => entry.parse::<i64>(setting)?.to_value(),
+        "t" => entry.parse::<u64>(setting)?.to_value(),
+        "s" => entry.string().to_value(),
+        "as" => entry.list().to_value(),
+        "au" => entry.parse_list::<u32>(setting)?.to_value(),
+        // SSIDs are written as strings when printable, with `;` escaped as `\;` before the keyfile
+        // escapes, so as `\\;` in the file, but older files have a single backslash
+        "ay" => read_byte_list(entry.value)
+            .unwrap_or_else(|| entry.string().replace("\\;", ";").into_bytes())
+            .to_value(),
+        _ => Err(entry.error(format!("{setting}.{key} cannot be read from a keyfile"))),
+    }
+}
+
+fn read_bool(entry: &KeyfileEntry<'_>, group: &str) -> Result<bool, Error> {
+    match entry.value {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(entry.invalid(group)),
+    }
+}
+
+/// Read bytes written as a list of integers, like `1;2;3;`.
+fn read_byte_list(value: &str) -> Option<Vec<u8>> {
+    if !value.contains(';') {
+        return None;
+    }
+
+    value
+        .split(';')
+        .filter(|byte| !byte.is_empty())
+        .map(|byte| byte.trim().parse().ok())
+        .collect()
+}
+
+/// Read a `[wireguard-peer.<public key>]` group into a peer of the `peers` property.
+fn read_peer(
+    group: &str,
+    public_key: &str,
+    group_entries: &[KeyfileEntry<'_>],
+) -> Result<Entry, Error> {
+    let mut peer = Entry::new();
+    peer.insert("public-key".into(), public_key.to_owned().to_value()?);
+    for entry in group_entries {
+        let value = match entry.key {
+            "allowed-ips" => entry.list().to_value()?,
+            "persistent-keepalive" | "preshared-key-flags" => {
+                entry.parse::<u32>(group)?.to_value()?
+            }
+            _ => entry.string().to_value()?,
+        };
+        peer.insert(entry.key.into(), value);
+    }
+    Ok(peer)
+}
+
+/// Read the `ipv4` or `ipv6` group, whose addresses, routes and routing rules are numbered keys.
+fn read_ip<A: IpFamily>(
+    setting: &str,
+    group_entries: &[KeyfileEntry<'_>],
+    properties: &mut Entry,
+) -> Result<(), Error>
+where
+    Vec<A>: SettingValue,
+{
+    // keyed by the index and the key, as NetworkManager reads both `address1` and `addresses1`
+    let mut addresses = BTreeMap::new();
+    let mut routes = BTreeMap::new();
+    let mut route_options = BTreeMap::new();
+    let mut rules = BTreeMap::new();
+    for entry in group_entries {
+        if let Some(index) = numbered(entry.key, &["address", "addresses"]) {
+            addresses.insert((index, entry.key), entry);
+        } else if let Some(index) = numbered(entry.key, &["route", "routes"]) {
+            routes.insert((index, entry.key), entry);
+        } else if let Some((index, route)) = entry
+            .key
+            .strip_suffix("_options")
+            .and_then(|key| numbered(key, &["route", "routes"]).map(|index| (index, key)))
+        {
+            route_options.insert((index, route), entry);
+        } else if let Some(index) = numbered(entry.key, &["routing-rule"]) {
+            rules.insert((index, entry.key), entry);
+        } else if entry.key == "dns" {
+            let servers = entry.parse_list::<A>(setting)?;
+            properties.insert("dns".into(), servers.to_value()?);
+        } else {
+            properties.insert(entry.key.into(), read_value(setting, entry)?);
+        }
+    }
+
+    if !addresses.is_empty() {
+        let mut address_data = Vec::new();
+        for entry in addresses.values() {
+            let value = entry.string();
+            let (network, gateway) = match value.split_once(',') {
+                Some((network, gateway)) => (network, Some(gateway)),
+                None => (value.as_str(), None),
+            };
+            let (address, prefix) = read_network::<A>(network, A::DEFAULT_PREFIX)
+                .ok_or_else(|| entry.invalid(setting))?;
+
+            let mut data = Entry::new();
+            data.insert("address".into(), address.to_string().to_value()?);
+            data.insert("prefix".into(), u32::from(prefix).to_value()?);
+            address_data.push(data);
+
+            // older keyfiles give the gateway with an address instead of as its own key
+            if let Some(gateway) = gateway.filter(|gateway| !gateway.is_empty()) {
+                let gateway = gateway.parse::<A>().map_err(|_| entry.invalid(setting))?;
+                if !properties.contains_key("gateway") {
+                    properties.insert("gateway".into(), gateway.to_string().to_value()?);
+                }
+            }
+        }
+        properties.insert("address-data".into(), entries_value(address_data)?);
+    }
+
+    if let Some(((_, route), entry)) = route_options
+        .iter()
+        .find(|(key, _)| !routes.contains_key(*key))
+    {
+        return Err(entry.error(format!("no {route} for these options")));
+    }
+    if !routes.is_empty() {
+        let mut route_data = Vec::new();
+        for (key, entry) in &routes {
+            let value = entry.string();
+            let mut parts = value.split(',');
+            let (dest, prefix) = parts
+                .next()
+                .and_then(|network| read_network::<A>(network, A::MAX_PREFIX))
+                .ok_or_else(|| entry.invalid(setting))?;
+
+            let mut data = Entry::new();
+            data.insert("dest".into(), dest.to_string().to_value()?);
+            data.insert("prefix".into(), u32::from(prefix).to_value()?);
+            if let Some(next_hop) = parts.next().filter(|next_hop| !next_hop.is_empty()) {
+                let next_hop = next_hop.parse::<A>().map_err(|_| entry.invalid(setting))?;
+                if !next_hop.is_unspecified() {
+                    data.insert("next-hop".into(), next_hop.to_string().to_value()?);
+                }
+            }
+            if let Some(metric) = parts.next().filter(|metric| !metric.is_empty()) {
+                let metric = metric.parse::<u32>().map_err(|_| entry.invalid(setting))?;
+                data.insert("metric".into(), metric.to_value()?);
+            }
+
+            if let Some(options) = route_options.get(key) {
+                for option in options
+                    .string()
+                    .split(',')
+                    .filter(|option| !option.is_empty())
+                {
+                    let (key, value) = option
+                        .split_once('=')
+                        .and_then(|(key, value)| Some((key, read_route_attribute(key, value)?)))
+                        .ok_or_else(|| options.error(format!("invalid route option `{option}`")))?;
+                    data.insert(key.into(), value);
+                }
+            }
+            route_data.push(data);
+        }
+        properties.insert("route-data".into(), entries_value(route_data)?);
+    }
+
+    if !rules.is_empty() {
+        let rules = rules
+            .values()
+            .map(|entry| read_rule::<A>(entry))
+            .collect::<Result<_, _>>()?;
+        properties.insert("routing-rules".into(), entries_value(rules)?);
+    }
+
+    Ok(())
+}
+
+/// The index of a numbered key like `address1`, where no number is index 0.
+fn numbered(key: &str, names: &[&str]) -> Option<u32> {
+    names.iter().find_map(|name| {
+        let index = key.strip_prefix(name)?;
+        if index.is_empty() {
+            Some(0)
+        } else if index.bytes().all(|c| c.is_ascii_digit()) {
+            index.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Read an address with an optional prefix length, like `10.0.0.0/8`.
+fn read_network<A: IpFamily>(network: &str, default_prefix: u8) -> Option<(A, u8)> {
+    let (address, prefix) = match network.trim().split_once('/') {
+        Some((address, prefix)) => (address.parse().ok()?, prefix.parse().ok()?),
+        None => (network.trim().parse().ok()?, default_prefix),
+    };
+    (prefix <= A::MAX_PREFIX).then_some((address, prefix))
+}
+
+/// Read a route attribute from `routeN_options`, typed as NetworkManager expects.
+fn read_route_attribute(key: &str, value: &str) -> Option<OwnedValue> {
+    Some(match key {
+        "advmss" | "cwnd" | "initcwnd" | "initrwnd" | "mtu" | "rto_min" | "table" | "weight"
+        | "window" => value.parse::<u32>().ok()?.to_value().ok()?,
+        "lock-advmss" | "lock-cwnd" | "lock-initcwnd" | "lock-initrwnd" | "lock-mtu"
+        | "lock-window" | "onlink" | "quickack" => value.parse::<bool>().ok()?.to_value().ok()?,
+        "scope" | "tos" => value.parse::<u8>().ok()?.into(),
+        "from" | "src" | "type" => value.to_owned().to_value().ok()?,
+        _ => return None,
+    })
+}
+
+/// Read a routing rule, in the form of `ip rule` like `priority 5 from 10.0.0.0/8 table 100`.
+fn read_rule<A: IpFamily>(entry: &KeyfileEntry<'_>) -> Result<Entry, Error> {
+    let rule = entry.string();
+    let mut data = Entry::new();
+    data.insert("family".into(), A::FAMILY.to_value()?);
+
+    let mut words = rule.split_whitespace();
+    while let Some(word) = words.next() {
+        if word == "not" {
+            data.insert("invert".into(), true.to_value()?);
+            continue;
+        }
+
+        let argument = words
+            .next()
+            .ok_or_else(|| entry.error(format!("missing argument to `{word}` in routing rule")))?;
+        let invalid = || {
+            entry.error(format!(
+                "invalid argument to `{word}` in routing rule: {argument}"
+            ))
+        };
+        match word {
+            "priority" => {
+                let priority = argument.parse::<u32>().map_err(|_| invalid())?;
+                data.insert("priority".into(), priority.to_value()?);
+            }
+            "from" | "to" if argument == "all" => {}
+            "from" | "to" => {
+                let (address, len) =
+                    read_network::<A>(argument, A::MAX_PREFIX).ok_or_else(invalid)?;
+                data.insert(word.into(), address.to_string().to_value()?);
+                data.insert(format!("{word}-len"), len.into());
+            }
+            "fwmark" => {
+                let (mark, mask) = match argument.split_once('/') {
+                    Some((mark, mask)) => (mark, Some(mask)),
+                    None => (argument, None),
+                };
+                let number = |number: &str| match number.strip_prefix("0x") {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => number.parse().ok(),
+                };
+                data.insert(
+                    "fwmark".into(),
+                    number(mark).ok_or_else(invalid)?.to_value()?,
+                );
+                let mask = mask.map_or(Some(u32::MAX), number).ok_or_else(invalid)?;
+                data.insert("fwmask".into(), mask.to_value()?);
+            }
+            "iif" => {
+                data.insert("iifname".into(), argument.to_owned().to_value()?);
+            }
+            "oif" => {
+                data.insert("oifname".into(), argument.to_owned().to_value()?);
+            }
+            "table" => {
+                let table = argument.parse::<u32>().map_err(|_| invalid())?;
+                data.insert("table".into(), table.to_value()?);
+            }
+            "tos" | "ipproto" => {
+                data.insert(
+                    word.into(),
+                    argument.parse::<u8>().map_err(|_| invalid())?.into(),
+                );
+            }
+            "suppress_prefixlength" => {
+                let length = argument.parse::<i32>().map_err(|_| invalid())?;
+                data.insert("suppress-prefixlength".into(), length.to_value()?);
+            }
+            _ => return Err(entry.error(format!("unsupported `{word}` in routing rule"))),
+        }
+    }
+    Ok(data)
+}
+
+fn write(raw: &RawSettings) -> Result<String, Error> {
+    let mut settings = raw.keys().map(String::as_str).collect::<Vec<_>>();
+    settings.sort_by_key(|setting| {
+        let order = match *setting {
+            ConnectionSetting::NAME => 0,
+            Ipv4Setting::NAME => 2,
+            Ipv6Setting::NAME => 3,
+            "proxy" => 4,
+            _ => 1,
+        };
+        (order, group_name(setting))
+    });
+
+    let mut keyfile = String::new();
+    for setting in settings {
+        for (group, group_entries) in write_setting(setting, &raw[setting])? {
+            if !keyfile.is_empty() {
+                keyfile.push('\n');
+            }
+            writeln!(keyfile, "[{group}]").unwrap();
+            for (key, value) in group_entries {
+                writeln!(keyfile, "{key}={value}").unwrap();
+            }
+        }
+    }
+    Ok(keyfile)
+}
+
+/// Write a setting as its group, followed by the groups of its WireGuard peers or VPN secrets.
+fn write_setting(setting: &str, properties: &Entry) -> Result<Vec<Group>, Error> {
+    let mut lines = Vec::new();
+    let mut groups = Vec::new();
+    let mut keys = properties.keys().map(String::as_str).collect::<Vec<_>>();
+    keys.sort_unstable();
+    for key in keys {
+        let value = &properties[key];
+        let unexpected =
+            || Error::InvalidSetting(format!("{setting}.{key} has an unexpected type"));
+        match (setting, key) {
+            (ConnectionSetting::NAME, "type") => {
+                let connection_type =
+                    String::from_value(clone_value(value)?).ok_or_else(unexpected)?;
+                lines.push((key.into(), escape(group_name(&connection_type), false)));
+            }
+            (Ipv6Setting::NAME, "addr-gen-mode") => {
+                let mode = i32::from_value(clone_value(value)?).ok_or_else(unexpected)?;
+                let mode = usize::try_from(mode)
+                    .ok()
+                    .and_then(|mode| ADDR_GEN_MODES.get(mode))
+                    .map_or_else(|| mode.to_string(), |mode| mode.to_string());
+                lines.push((key.into(), mode));
+            }
+            // legacy forms NetworkManager sends alongside `address-data` and `route-data`
+            (Ipv4Setting::NAME | Ipv6Setting::NAME, "addresses" | "routes") => {}
+            (Ipv4Setting::NAME, "dns") => {
+                let servers =
+                    Vec::<Ipv4Addr>::from_value(clone_value(value)?).ok_or_else(unexpected)?;
+                lines.push((
+                    key.into(),
+                    write_list(servers.iter().map(ToString::to_string)),
+                ));
+            }
+            (Ipv6Setting::NAME, "dns") => {
+                let servers =
+                    Vec::<Ipv6Addr>::from_value(clone_value(value)?).ok_or_else(unexpected)?;
+                lines.push((
+                    key.into(),
+                    write_list(servers.iter().map(ToString::to_string)),
+                ));
+            }
+            (Ipv4Setting::NAME | Ipv6Setting::NAME, "address-data") => {
+                for (index, data) in entries(clone_value(value)?)
+                    .ok_or_else(unexpected)?
+                    .iter()
+                    .enumerate()
+                {
+                    let address = get::<String>(data, "address").ok_or_else(unexpected)?;
+                    let prefix = get::<u32>(data, "prefix").ok_or_else(unexpected)?;
+                    lines.push((
+                        format!("address{}", index + 1),
+                        format!("{address}/{prefix}"),
+                    ));
+                }
+            }
+            (Ipv4Setting::NAME | Ipv6Setting::NAME, "route-data") => {
+                let unspecified = if setting == Ipv4Setting::NAME {
+                    "0.0.0.0"
+                } else {
+                    "::"
+                };
+                for (index, data) in entries(clone_value(value)?)
+                    .ok_or_else(unexpected)?
+                    .iter()
+                    .enumerate()
+                {
+                    let dest = get::<String>(data, "dest").ok_or_else(unexpected)?;
+                    let prefix = get::<u32>(data, "prefix").ok_or_else(unexpected)?;
+                    let next_hop = get::<String>(data, "next-hop");
+                    let metric = get::<u32>(data, "metric");
+
+                    let mut route = format!("{dest}/{prefix}");
+                    if next_hop.is_some() || metric.is_some() {
+                        write!(route, ",{}", next_hop.as_deref().unwrap_or(unspecified)).unwrap();
+                    }
+                    if let Some(metric) = metric {
+                        write!(route, ",{metric}").unwrap();
+                    }
+                    lines.push((format!("route{}", index + 1), route));
+
+                    let mut options = data
+                        .iter()
+                        .filter(|(key, _)| {
+                            !["dest", "prefix", "next-hop", "metric"].contains(&key.as_str())
+                        })
+                        .map(|(key, value)| Some(format!("{key}={}", write_scalar(value)?)))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(unexpected)?;
+                    if !options.is_empty() {
+                        options.sort_unstable();
+                        lines.push((format!("route{}_options", index + 1), options.join(",")));
+                    }
+                }
+            }
+            (Ipv4Setting::NAME | Ipv6Setting::NAME, "routing-rules") => {
+                for (index, data) in entries(clone_value(value)?)
+                    .ok_or_else(unexpected)?
+                    .iter()
+                    .enumerate()
+                {
+                    let rule = write_rule(data).map_err(|message| {
+                        Error::InvalidSetting(format!("{setting}.{key} {message}"))
+                    })?;
+                    lines.push((format!("routing-rule{}", index + 1), rule));
+                }
+            }
+            (BondSetting::NAME, "options") | (VPN, "data") => {
+                let options = HashMap::<String, String>::from_value(clone_value(value)?)
+                    .ok_or_else(unexpected)?;
+                lines.extend(write_dict(options));
+            }
+            (VPN, "secrets") => {
+                let secrets = HashMap::<String, String>::from_value(clone_value(value)?)
+                    .ok_or_else(unexpected)?;
+                groups.push((VPN_SECRETS.into(), write_dict(secrets)));
+            }
+            // keyfiles only have the newer form, which takes the special values too
+            (_, "assigned-mac-address") => {
+                let address = String::from_value(clone_value(value)?).ok_or_else(unexpected)?;
+                lines.push((CLONED_MAC_ADDRESS.into(), escape(&address, false)));
+            }
+            (_, CLONED_MAC_ADDRESS) if properties.contains_key("assigned-mac-address") => {}
+            (WireGuardSetting::NAME, "peers") => {
+                for peer in entries(clone_value(value)?).ok_or_else(unexpected)? {
+                    groups.push(write_peer(peer).ok_or_else(unexpected)?);
+                }
+            }
+            _ => lines.push((
+                key.into(),
+                write_value(setting, key, value).ok_or_else(unexpected)?,
+            )),
+        }
+    }
+
+    groups.insert(0, (group_name(setting).into(), lines));
+    Ok(groups)
+}
+
+/// Write a property by its D-Bus type.
+fn write_value(setting: &str, key: &str, value: &OwnedValue) -> Option<String> {
+    let signature = value.value_signature().to_string();
+    match signature.as_str() {
+        "as" => Some(write_list(Vec::<String>::from_value(
+            clone_value(value).ok()?,
+        )?)),
+        "au" => Some(write_list(
+            Vec::<u32>::from_value(clone_value(value).ok()?)?
+                .iter()
+                .map(ToString::to_string),
+        )),
+        "ay" => {
+            let bytes = Vec::<u8>::from_value(clone_value(value).ok()?)?;
+            Some(
+                if setting == Ieee8021xSetting::NAME && CERTIFICATES.contains(&key) {
+                    match Certificate::from_bytes(bytes) {
+                        Certificate::Path(path) => escape(&path.to_string_lossy(), false),
+                        Certificate::Pkcs11(uri) => escape(&uri, false),
+                        Certificate::Blob(data) => {
+                            format!("data:;base64,{}", STANDARD.encode(data))
+                        }
+                    }
+                } else if MAC_ADDRESSES.contains(&key) {
+                    bytes
+                        .iter()
+                        .map(|octet| format!("{octet:02X}"))
+                        .collect::<Vec<_>>()
+                        .join(":")
+                } else if setting == WirelessSetting::NAME
+                    && key == "ssid"
+                    && bytes.iter().all(|c| c.is_ascii_graphic() || *c == b' ')
+                {
+                    escape(&String::from_utf8(bytes).ok()?.replace(';', "\\;"), false)
+                } else {
+                    write_list(bytes.iter().map(ToString::to_string))
+                },
+            )
+        }
+        _ => write_scalar(value),
+    }
+}
+
+/// Write a boolean, number or string.
+fn write_scalar(value: &OwnedValue) -> Option<String> {
+    let value = clone_value(value).ok()?;
+    Some(match value.value_signature().to_string().as_str() {
+        "b" => bool::from_value(value)?.to_string(),
+        "y" => u8::try_from(value).ok()?.to_string(),
+        "i" => i32::from_value(value)?.to_string(),
+        "u" => u32::from_value(value)?.to_string(),
+        "x" => i64::from_value(value)?.to_string(),
+        "t" => u64::from_value(value)?.to_string(),
+        "s" => escape(&String::from_value(value)?, false),
+        _ => return None,
+    })
+}
+
+fn write_dict(dict: HashMap<String, String>) -> Vec<(String, String)> {
+    let mut lines = dict
+        .into_iter()
+        .map(|(key, value)| (key, escape(&value, false)))
+        .collect::<Vec<_>>();
+    lines.sort_unstable();
+    lines
+}
+
+fn write_peer(mut peer: Entry) -> Option<Group> {
+    let public_key = String::from_value(peer.remove("public-key")?)?;
+    let mut lines = peer
+        .iter()
+        .map(|(key, value)| Some((key.clone(), write_value(WIREGUARD_PEER, key, value)?)))
+        .collect::<Option<Vec<_>>>()?;
+    lines.sort_unstable();
+    Some((format!("{WIREGUARD_PEER}{public_key}"), lines))
+}
+
+/// Write a routing rule in the form of `ip rule`.
+fn write_rule(data: &Entry) -> Result<String, String> {
+    const KEYS: &[&str] = &[
+        "family",
+        "invert",
+        "priority",
+        "from",
+        "from-len",
+        "to",
+        "to-len",
+        "tos",
+        "ipproto",
+        "fwmark",
+        "fwmask",
+        "iifname",
+        "oifname",
+        "table",
+        "suppress-prefixlength",
+    ];
+    if let Some(key) = data.keys().find(|key| !KEYS.contains(&key.as_str())) {
+        return Err(format!("has `{key}`, which keyfiles don't support"));
+    }
+
+    let mut words = Vec::new();
+    if get::<bool>(data, "invert") == Some(true) {
+        words.push("not".to_owned());
+    }
+    if let Some(priority) = get::<u32>(data, "priority") {
+        words.push(format!("priority {priority}"));
+    }
+    for direction in ["from", "to"] {
+        if let Some(address) = get::<String>(data, direction) {
+            let len = data
+                .get(&format!("{direction}-len"))
+                .and_then(|len| u8::try_from(clone_value(len).ok()?).ok())
+                .unwrap_or(0);
+            words.push(format!("{direction} {address}/{len}"));
+        }
+    }
+    for key in ["tos", "ipproto"] {
+        if let Some(value) = data.get(key).and_then(write_scalar) {
+            words.push(format!("{key} {value}"));
+        }
+    }
+    if let Some(fwmark) = get::<u32>(data, "fwmark") {
+        let fwmask = get::<u32>(data, "fwmask").unwrap_or(u32::MAX);
+        words.push(format!("fwmark {fwmark:#x}/{fwmask:#x}"));
+    }
+    if let Some(iifname) = get::<String>(data, "iifname") {
+        words.push(format!("iif {iifname}"));
+    }
+    if let Some(oifname) = get::<String>(data, "oifname") {
+        words.push(format!("oif {oifname}"));
+    }
+    if let Some(table) = get::<u32>(data, "table") {
+        words.push(format!("table {table}"));
+    }
+    if let Some(length) = get::<i32>(data, "suppress-prefixlength") {
+        words.push(format!("suppress_prefixlength {length}"));
+    }
+    Ok(words.join(" "))
+}
+
+fn get<T: SettingValue>(entry: &Entry, key: &str) -> Option<T> {
+    T::from_value(clone_value(entry.get(key)?).ok()?)
+}
+
+/// The name of the setting a keyfile group holds.
+fn setting_name(group: &str) -> &str {
+    ALIASES
+        .iter()
+        .find(|(_, alias)| *alias == group)
+        .map_or(group, |(setting, _)| setting)
+}
+
+/// The name of the keyfile group holding a setting.
+fn group_name(setting: &str) -> &str {
+    ALIASES
+        .iter()
+        .find(|(name, _)| *name == setting)
+        .map_or(setting, |(_, alias)| alias)
+}
+
+/// Undo the escapes of a keyfile string: `\s`, `\n`, `\t`, `\r` and `\\`.
+///
+/// Other escapes are kept as-is, like `\;` which NetworkManager uses within SSIDs.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some('\\') => unescaped.push('\\'),
+            Some(c) => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Split a keyfile list on `;`, except where escaped as `\;`, and unescape the items.
+fn unescape_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => items.push(unescape(&std::mem::take(&mut item))),
+            '\\' => match chars.next() {
+                Some(';') => item.push(';'),
+                Some(c) => {
+                    item.push('\\');
+                    item.push(c);
+                }
+                None => item.push('\\'),
+            },
+            c => item.push(c),
+        }
+    }
+    if !item.is_empty() {
+        items.push(unescape(&item));
+    }
+    items
+}
+
+/// Escape a string for a keyfile, including `;` for items of lists.
+fn escape(value: &str, list: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (index, c) in value.chars().enumerate() {
+        match c {
+            ' ' if index == 0 => escaped.push_str("\\s"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' => escaped.push_str("\\\\"),
+            ';' if list => escaped.push_str("\\;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_list(items: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    items
+        .into_iter()
+        .map(|item| format!("{};", escape(item.as_ref(), true)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setting::{
+        EapMethod, Ipv4Method, KeyManagement, Phase2Auth, WireGuardPublicKey, WirelessMode,
+    };
+
+    /// Parse a keyfile, and check that writing it and parsing it again gives the same settings.
+    fn round_trip(keyfile: &str) -> ConnectionSettings {
+        let settings = ConnectionSettings::from_keyfile(keyfile).unwrap();
+        let written = settings.to_keyfile().unwrap();
+        assert_eq!(
+            ConnectionSettings::from_keyfile(&written).unwrap(),
+            settings,
+            "{written}"
+        );
+        settings
+    }
+
+    fn line<'a>(keyfile: &'a str, key: &str) -> Option<&'a str> {
+        keyfile
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+    }
+
+    const ETHERNET: &str = "\
+[connection]
+id=Wired static
+uuid=2d1b5e0a-3c53-4b9d-9c1f-8a6a8e3a4b10
+type=ethernet
+autoconnect-priority=10
+interface-name=eth0
+
+[ethernet]
+cloned-mac-address=stable
+mac-address=52:54:00:12:34:56
+
+[ipv4]
+address1=192.168.1.10/24,192.168.1.1
+address2=10.1.0.10/16
+dns=192.168.1.1;8.8.8.8;
+dns-search=example.com;
+method=manual
+route1=10.10.0.0/16,192.168.1.254,100
+route1_options=mtu=1400,onlink=true,table=100
+route2=172.16.0.0/12
+routing-rule1=priority 5 from 192.168.1.0/24 table 100
+
+[ipv6]
+addr-gen-mode=stable-privacy
+address1=fd00::10/64,fd00::1
+method=manual
+route1=fd01::/64,::,50
+
+[proxy]
+";
+
+    #[test]
+    fn ethernet_with_static_addresses() {
+        let settings = round_trip(ETHERNET);
+        let connection = settings.connection.as_ref().unwrap();
+        assert_eq!(connection.id.as_deref(), Some("Wired static"));
+        assert_eq!(
+            connection.connection_type.as_deref(),
+            Some("802-3-ethernet")
+        );
+        assert_eq!(connection.interface_name.as_deref(), Some("eth0"));
+
+        let ethernet = settings.ethernet.as_ref().unwrap();
+        assert_eq!(
+            ethernet.mac_address.as_deref(),
+            Some(&[0x52, 0x54, 0, 0x12, 0x34, 0x56][..])
+        );
+        assert_eq!(ethernet.assigned_mac_address.as_deref(), Some("stable"));
+
+        let ipv4 = settings.ipv4.as_ref().unwrap();
+        assert_eq!(ipv4.method, Some(Ipv4Method::Manual));
+        let addresses = ipv4.addresses.as_ref().unwrap();
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses[0].address, Ipv4Addr::new(192, 168, 1, 10));
+        assert_eq!(addresses[0].prefix, 24);
+        assert_eq!(addresses[1].prefix, 16);
+        assert_eq!(ipv4.gateway, Some(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(
+            ipv4.dns.as_deref(),
+            Some(&[Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(8, 8, 8, 8)][..])
+        );
+        assert_eq!(
+            ipv4.dns_search.as_deref(),
+            Some(&["example.com".into()][..])
+        );
+
+        let routes = ipv4.routes.as_ref().unwrap();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].dest, Ipv4Addr::new(10, 10, 0, 0));
+        assert_eq!(routes[0].prefix, 16);
+        assert_eq!(routes[0].next_hop, Some(Ipv4Addr::new(192, 168, 1, 254)));
+        assert_eq!(routes[0].metric, Some(100));
+        assert_eq!(routes[0].table, Some(100));
+        assert_eq!(
+            u32::from_value(clone_value(&routes[0].attributes["mtu"]).unwrap()),
+            Some(1400)
+        );
+        assert_eq!(
+            bool::from_value(clone_value(&routes[0].attributes["onlink"]).unwrap()),
+            Some(true)
+        );
+        assert_eq!(routes[1].next_hop, None);
+        assert_eq!(routes[1].metric, None);
+
+        let rules = ipv4.routing_rules.as_ref().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].priority, Some(5));
+        assert_eq!(rules[0].from, Some((Ipv4Addr::new(192, 168, 1, 0), 24)));
+        assert_eq!(rules[0].table, Some(100));
+
+        let ipv6 = settings.ipv6.as_ref().unwrap();
+        assert_eq!(ipv6.addr_gen_mode, Some(1));
+        assert_eq!(ipv6.gateway, Some("fd00::1".parse().unwrap()));
+        let routes = ipv6.routes.as_ref().unwrap();
+        assert_eq!(routes[0].next_hop, None);
+        assert_eq!(routes[0].metric, Some(50));
+
+        let written = settings.to_keyfile().unwrap();
+        assert_eq!(line(&written, "type"), Some("ethernet"));
+        assert_eq!(line(&written, "cloned-mac-address"), Some("stable"));
+        assert_eq!(line(&written, "mac-address"), Some("52:54:00:12:34:56"));
+        assert_eq!(line(&written, "address1"), Some("192.168.1.10/24"));
+        assert_eq!(line(&written, "gateway"), Some("192.168.1.1"));
+        assert_eq!(
+            line(&written, "route1"),
+            Some("10.10.0.0/16,192.168.1.254,100")
+        );
+        assert_eq!(
+            line(&written, "route1_options"),
+            Some("mtu=1400,onlink=true,table=100")
+        );
+        assert_eq!(
+            line(&written, "routing-rule1"),
+            Some("priority 5 from 192.168.1.0/24 table 100")
+        );
+        assert_eq!(line(&written, "addr-gen-mode"), Some("stable-privacy"));
+        assert_eq!(line(&written, "dns"), Some("192.168.1.1;8.8.8.8;"));
+        assert!(written.starts_with("[connection]\n"), "{written}");
+        assert!(written.contains("\n[ethernet]\n"), "{written}");
+        assert!(written.contains("\n[ipv6]\n"), "{written}");
+    }
+
+    #[test]
+    fn both_forms_of_numbered_keys() {
+        let settings = round_trip(
+            "[ipv4]
+address1=10.0.0.1/24
+addresses1=10.0.1.1/24
+method=manual
+route1=10.1.0.0/16
+route1_options=mtu=1400
+routes1=10.2.0.0/16
+routes1_options=mtu=1300
+",
+        );
+        let ipv4 = settings.ipv4.as_ref().unwrap();
+        let addresses = ipv4.addresses.as_ref().unwrap();
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses[0].address, Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(addresses[1].address, Ipv4Addr::new(10, 0, 1, 1));
+
+        let routes = ipv4.routes.as_ref().unwrap();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].dest, Ipv4Addr::new(10, 1, 0, 0));
+        assert_eq!(
+            u32::from_value(clone_value(&routes[0].attributes["mtu"]).unwrap()),
+            Some(1400)
+        );
+        assert_eq!(routes[1].dest, Ipv4Addr::new(10, 2, 0, 0));
+        assert_eq!(
+            u32::from_value(clone_value(&routes[1].attributes["mtu"]).unwrap()),
+            Some(1300)
+        );
+
+        let orphan = "[ipv4]\nroute1=10.1.0.0/16\nroutes1_options=mtu=1300\n";
+        assert!(ConnectionSettings::from_keyfile(orphan).is_err());
+    }
+
+    #[test]
+    fn addr_gen_modes() {
+        for (index, mode) in ADDR_GEN_MODES.iter().enumerate() {
+            let keyfile = format!("[ipv6]\naddr-gen-mode={mode}\nmethod=auto\n");
+            let settings = round_trip(&keyfile);
+            assert_eq!(
+                settings.ipv6.as_ref().unwrap().addr_gen_mode,
+                Some(index as i32)
+            );
+            let written = settings.to_keyfile().unwrap();
+            assert_eq!(line(&written, "addr-gen-mode"), Some(*mode));
+        }
+
+        let settings = round_trip("[ipv6]\naddr-gen-mode=1\n");
+        assert_eq!(settings.ipv6.unwrap().addr_gen_mode, Some(1));
+        assert!(ConnectionSettings::from_keyfile("[ipv6]\naddr-gen-mode=bogus\n").is_err());
+    }
+
+    #[test]
+    fn cloned_mac_address() {
+        for mode in MAC_ADDRESS_MODES {
+            let settings = round_trip(&format!("[wifi]\ncloned-mac-address={mode}\nssid=home\n"));
+            let wireless = settings.wireless.as_ref().unwrap();
+            assert_eq!(wireless.assigned_mac_address.as_deref(), Some(*mode));
+            assert!(!wireless.extra.contains_key(CLONED_MAC_ADDRESS));
+            let written = settings.to_keyfile().unwrap();
+            assert_eq!(line(&written, "cloned-mac-address"), Some(*mode));
+        }
+
+        let settings = round_trip("[ethernet]\ncloned-mac-address=02:00:00:AA:BB:CC\n");
+        let ethernet = settings.ethernet.as_ref().unwrap();
+        assert_eq!(
+            ethernet.assigned_mac_address.as_deref(),
+            Some("02:00:00:AA:BB:CC")
+        );
+        let written = settings.to_keyfile().unwrap();
+        assert_eq!(
+            written.matches("cloned-mac-address=").count(),
+            1,
+            "{written}"
+        );
+        assert_eq!(
+            line(&written, "cloned-mac-address"),
+            Some("02:00:00:AA:BB:CC")
+        );
+
+        assert!(
+            ConnectionSettings::from_keyfile("[ethernet]\ncloned-mac-address=bogus\n").is_err()
+        );
+    }
+
+    #[test]
+    fn assigned_mac_address_from_dbus() {
+        // NetworkManager sends the deprecated bytes alongside the newer string
+        let mut settings = ConnectionSettings::default();
+        let mut wireless = WirelessSetting {
+            assigned_mac_address: Some("random".into()),
+            ..Default::default()
+        };
+        wireless.extra.insert(
+            CLONED_MAC_ADDRESS.into(),
+            vec![2u8, 0, 0, 0xAA, 0xBB, 0xCC].to_value().unwrap(),
+        );
+        settings.wireless = Some(wireless);
+
+        let written = settings.to_keyfile().unwrap();
+        assert_eq!(written, "[wifi]\ncloned-mac-address=random\n");
+    }
+
+    const WIFI: &str = "\
+[connection]
+id=Home Wi-Fi
+uuid=8e0e8f55-7c1e-4d8e-b2a5-7f1a6c3c9e21
+type=wifi
+permissions=
+
+[wifi]
+mode=infrastructure
+ssid=my\\\\;home\\\\;network
+
+[wifi-security]
+auth-alg=open
+key-mgmt=wpa-psk
+psk=correct horse battery staple
+
+[ipv4]
+method=auto
+
+[ipv6]
+addr-gen-mode=default
+method=auto
+
+[proxy]
+";
+
+    #[test]
+    fn wifi_with_escaped_ssid() {
+        let settings = round_trip(WIFI);
+        let wireless = settings.wireless.as_ref().unwrap();
+        assert_eq!(wireless.ssid.as_deref(), Some(&b"my;home;network"[..]));
+        assert_eq!(wireless.mode, Some(WirelessMode::Infrastructure));
+        let security = settings.wireless_security.as_ref().unwrap();
+        assert_eq!(security.key_mgmt, Some(KeyManagement::WpaPsk));
+        assert_eq!(
+            security.psk.as_deref(),
+            Some("correct horse battery staple")
+        );
+        assert!(settings.validate().is_ok());
+
+        let written = settings.to_keyfile().unwrap();
+        assert_eq!(line(&written, "ssid"), Some("my\\\\;home\\\\;network"));
+        assert_eq!(line(&written, "type"), Some("wifi"));
+        assert!(written.contains("\n[wifi-security]\n"), "{written}");
+    }
+
+    #[test]
+    fn ssid_forms() {
+        for (ssid, bytes) in [
+            ("plain", &b"plain"[..]),
+            ("\\sleading space", b" leading space"),
+            ("trailing space ", b"trailing space "),
+            ("back\\\\slash", b"back\\slash"),
+            ("semi\\\\;colon", b"semi;colon"),
+            ("single\\;backslash", b"single;backslash"),
+            ("0;159;146;150;", &[0, 159, 146, 150]),
+            ("72;105;33;", b"Hi!"),
+        ] {
+            let settings = round_trip(&format!("[wifi]\nssid={ssid}\n"));
+            assert_eq!(
+                settings.wireless.as_ref().unwrap().ssid.as_deref(),
+                Some(bytes),
+                "{ssid}"
+            );
+        }
+
+        // printable SSIDs are written as strings, others as bytes
+        let ssid = |bytes: &[u8]| {
+            let settings = ConnectionSettings {
+                wireless: Some(WirelessSetting {
+                    ssid: Some(bytes.to_vec()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            let written = settings.to_keyfile().unwrap();
+            line(&written, "ssid").unwrap().to_owned()
+        };
+        assert_eq!(ssid(b" a;b"), "\\sa\\\\;b");
+        assert_eq!(ssid(&[0, 159, 146, 150]), "0;159;146;150;");
+        assert_eq!(ssid("café".as_bytes()), "99;97;102;195;169;");
+    }
+
+    #[test]
+    fn wpa_none() {
+        let settings = round_trip(
+            "[wifi]\nmode=adhoc\nssid=ibss\n\n[wifi-security]\nkey-mgmt=wpa-none\npsk=passphrase\n",
+        );
+        let security = settings.wireless_security.as_ref().unwrap();
+        assert_eq!(security.key_mgmt, None);
+        assert!(security.extra.contains_key("key-mgmt"));
+        assert!(security.validate().is_ok());
+        let written = settings.to_keyfile().unwrap();
+        assert_eq!(line(&written, "key-mgmt"), Some("wpa-none"));
+    }
+
+    const ENTERPRISE: &str = "\
+[connection]
+id=eduroam
+uuid=5f6b2c1e-2a0d-4c3e-9a7b-6d8e9f0a1b2c
+type=wifi
+
+[wifi]
+mode=infrastructure
+ssid=eduroam
+
+[wifi-security]
+key-mgmt=wpa-eap
+
+[802-1x]
+anonymous-identity=anonymous@example.edu
+ca-cert=/etc/ssl/certs/eduroam-ca.pem
+client-cert=file:///home/user/.certs/client\\spem.crt
+domain-suffix-match=example.edu
+eap=peap;
+identity=user@example.edu
+password=hunter2
+phase2-auth=mschapv2
+phase2-ca-cert=data:;base64,LS0tLS1CRUdJTg==
+private-key=pkcs11:token=Smartcard;object=key
+
+[ipv4]
+method=auto
+";
+
+    #[test]
+    fn enterprise_certificates() {
+        let settings = round_trip(ENTERPRISE);
+        let ieee8021x = settings.ieee8021x.as_ref().unwrap();
+        assert_eq!(ieee8021x.eap.as_deref(), Some(&[EapMethod::Peap][..]));
+        assert_eq!(ieee8021x.phase2_auth, Some(Phase2Auth::Mschapv2));
+        assert_eq!(
+            ieee8021x.ca_cert,
+            Some(Certificate::path("/etc/ssl/certs/eduroam-ca.pem"))
+        );
+        assert_eq!(
+            ieee8021x.client_cert,
+            Some(Certificate::path("/home/user/.certs/client pem.crt"))
+        );
+        assert_eq!(
+            ieee8021x.phase2_ca_cert,
+            Some(Certificate::Blob(b"-----BEGIN".to_vec()))
+        );
+        assert_eq!(
+            ieee8021x.private_key,
+            Some(Certificate::Pkcs11(
+                "pkcs11:token=Smartcard;object=key".into()
+            ))
+        );
+
+        let written = settings.to_keyfile().unwrap();
+        assert_eq!(
+            line(&written, "ca-cert"),
+            Some("/etc/ssl/certs/eduroam-ca.pem")
+        );
+        assert_eq!(
+            line(&written, "client-cert"),
+            Some("/home/user/.certs/client pem.crt")
+        );
+        assert_eq!(
+            line(&written, "phase2-ca-cert"),
+            Some("data:;base64,LS0tLS1CRUdJTg==")
+        );
+        assert_eq!(
+            line(&written, "private-key"),
+            Some("pkcs11:token=Smartcard;object=key")
+        );
+        assert_eq!(line(&written, "eap"), Some("peap;"));
+        assert!(written.contains("\n[802-1x]\n"), "{written}");
+
+        let invalid = ENTERPRISE.replace("LS0tLS1CRUdJTg==", "not base64!");
+        assert!(ConnectionSettings::from_keyfile(&invalid).is_err());
+    }
+
+    const WIREGUARD: &str = "\
+[connection]
+id=wg0
+uuid=1c3f5a7e-9b2d-4f6a-8c0e-2d4f6a8c0e2d
+type=wireguard
+interface-name=wg0
+
+[wireguard]
+listen-port=51820
+private-key=dwdtCnMYpX08FsFyUbJmRd9ML4frwJkqsXf7pR25LCo=
+private-key-flags=0
+
+[wireguard-peer.hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo=]
+allowed-ips=10.0.0.0/24;fd00::/64;
+endpoint=vpn.example.com:51820
+persistent-keepalive=25
+preshared-key=AwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwM=
+preshared-key-flags=0
+
+[wireguard-peer.AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=]
+allowed-ips=10.0.1.0/24;
+
+[ipv4]
+address1=10.0.0.2/24
+method=manual
+
+[ipv6]
+addr-gen-mode=default
+method=disabled
+
+[proxy]
+";
+
+    #[test]
+    fn wireguard_peers() {
+        let settings = round_trip(WIREGUARD);
+        let wireguard = settings.wireguard.as_ref().unwrap();
+        assert_eq!(wireguard.listen_port, Some(51820));
+        assert_eq!(
+            wireguard.private_key.as_deref(),
+            Some("dwdtCnMYpX08FsFyUbJmRd9ML4frwJkqsXf7pR25LCo=")
+        );
+        assert!(wireguard.validate().is_ok());
+
+        let peers = wireguard.peers.as_ref().unwrap();
+        assert_eq!(peers.len(), 2);
+        let key: WireGuardPublicKey = "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo="
+            .parse()
+            .unwrap();
+        let peer = wireguard.peer(&key).unwrap();
+        assert_eq!(
+            peer.allowed_ips.as_deref(),
+            Some(&["10.0.0.0/24".into(), "fd00::/64".into()][..])
+        );
+        assert_eq!(peer.endpoint.as_deref(), Some("vpn.example.com:51820"));
+        assert_eq!(peer.persistent_keepalive, Some(25));
+        assert!(peer.preshared_key.is_some());
+
+        let written = settings.to_keyfile().unwrap();
+        assert!(
+            written.contains(
+                "\n[wireguard-peer.hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo=]\n\
+                 allowed-ips=10.0.0.0/24;fd00::/64;\n\
+                 endpoint=vpn.example.com:51820\n"
+            ),
+            "{written}"
+        );
+        assert!(
+            written.contains(
+                "\n[wireguard-peer.AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=]\n\
+                 allowed-ips=10.0.1.0/24;\n"
+            ),
+            "{written}"
+        );
+    }
+
+    const VPN_KEYFILE: &str = "\
+[connection]
+id=Office VPN
+uuid=0b9a8c7d-6e5f-4a3b-2c1d-0e9f8a7b6c5d
+type=vpn
+autoconnect=false
+
+[vpn]
+ca=/home/user/.cert/ca.crt
+connection-type=password
+password-flags=0
+remote=vpn.example.com
+username=user
+service-type=org.freedesktop.NetworkManager.openvpn
+
+[vpn-secrets]
+password=hunter2\\sand more
+
+[ipv4]
+method=auto
+never-default=true
+";
+
+    #[test]
+    fn vpn_data_and_secrets() {
+        let settings = round_trip(VPN_KEYFILE);
+        let connection = settings.connection.as_ref().unwrap();
+        assert_eq!(connection.connection_type.as_deref(), Some("vpn"));
+        assert_eq!(connection.autoconnect, Some(false));
+        assert_eq!(settings.ipv4.as_ref().unwrap().never_default, Some(true));
+
+        let vpn = &settings.other[VPN];
+        assert_eq!(
+            String::from_value(clone_value(&vpn["service-type"]).unwrap()).as_deref(),
+            Some("org.freedesktop.NetworkManager.openvpn")
+        );
+        let data =
+            HashMap::<String, String>::from_value(clone_value(&vpn["data"]).unwrap()).unwrap();
+        assert_eq!(data.len(), 5);
+        assert_eq!(data["remote"], "vpn.example.com");
+        assert_eq!(data["username"], "user");
+        let secrets =
+            HashMap::<String, String>::from_value(clone_value(&vpn["secrets"]).unwrap()).unwrap();
+        assert_eq!(secrets["password"], "hunter2 and more");
+
+        let written = settings.to_keyfile().unwrap();
+        assert!(
+            written.contains("\n[vpn-secrets]\npassword=hunter2 and more\n"),
+            "{written}"
+        );
+        assert_eq!(
+            line(&written, "service-type"),
+            Some("org.freedesktop.NetworkManager.openvpn")
+        );
+        assert_eq!(line(&written, "remote"), Some("vpn.example.com"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (keyfile, line_number) in [
+            ("id=orphan\n", 1),
+            ("[connection]\nnot a key\n", 2),
+            ("[connection]\nautoconnect=maybe\n", 2),
+            ("[ipv4]\naddress1=10.0.0.1/33\n", 2),
+            ("[ipv4]\nroute1_options=mtu=1400\n", 2),
+            ("[ipv4]\nroute1=10.0.0.0/8\nroute1_options=colour=blue\n", 3),
+            ("[ipv4]\nrouting-rule1=priority\n", 2),
+            ("[ipv4]\nrouting-rule1=blackhole 5\n", 2),
+            ("[ethernet]\nmac-address=not a mac\n", 2),
+        ] {
+            match ConnectionSettings::from_keyfile(keyfile) {
+                Err(Error::Parse { line, .. }) => assert_eq!(line, line_number, "{keyfile}"),
+                other => panic!("expected a parse error for {keyfile:?}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn escapes() {
+        for value in [
+            "plain",
+            " leading",
+            "tab\there",
+            "new\nline",
+            "back\\slash",
+            "a;b",
+        ] {
+            assert_eq!(unescape(&escape(value, false)), value);
+            assert_eq!(unescape_list(&write_list([value])), [value]);
+        }
+        assert_eq!(unescape_list("a;b\\;c;;d"), ["a", "b;c", "", "d"]);
+    }
+}
@@ -0,0 +1,126 @@
+use zbus::zvariant::{OwnedValue, Signature};
+
+use crate::Error;
+
+use super::{setting, SettingValue};
+
+setting! {
+    /// MACVLAN settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-macvlan.html)
+    pub struct MacvlanSetting = "macvlan" {
+        /// The interface name or connection UUID of the parent device.
+        parent: String = "parent",
+
+        /// The MACVLAN mode.
+        mode: MacvlanMode = "mode",
+
+        /// Whether the parent device is put in promiscuous mode.
+        ///
+        /// This can only be disabled in [`MacvlanMode::Passthru`] mode.
+        promiscuous: bool = "promiscuous",
+
+        /// Whether to create a MACVTAP rather than a MACVLAN.
+        tap: bool = "tap",
+    }
+}
+
+impl MacvlanSetting {
+    /// Create settings for a MACVLAN on a parent device.
+    ///
+    /// The parent is either an interface name or the UUID of the parent's connection.
+    pub fn new(parent: impl Into<String>, mode: MacvlanMode) -> Self {
+        Self {
+            parent: Some(parent.into()),
+            mode: Some(mode),
+            ..Default::default()
+        }
+    }
+
+    /// Create a MACVTAP rather than a MACVLAN.
+    pub fn tap(mut self) -> Self {
+        self.tap = Some(true);
+        self
+    }
+}
+
+/// The mode of a MACVLAN, which determines how it communicates with other MACVLANs on the same
+/// parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MacvlanMode {
+    /// All traffic goes through the external switch, including to other MACVLANs.
+    Vepa = 1,
+
+    /// MACVLANs on the same parent can communicate directly.
+    Bridge = 2,
+
+    /// MACVLANs on the same parent cannot communicate with each other.
+    Private = 3,
+
+    /// The MACVLAN takes over the parent, which can only have one.
+    Passthru = 4,
+
+    /// Only traffic from a list of source MAC addresses is accepted.
+    Source = 5,
+}
+
+impl SettingValue for MacvlanMode {
+    const SIGNATURE: &'static Signature = <u32 as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        match u32::try_from(value).ok()? {
+            1 => Some(MacvlanMode::Vepa),
+            2 => Some(MacvlanMode::Bridge),
+            3 => Some(MacvlanMode::Private),
+            4 => Some(MacvlanMode::Passthru),
+            5 => Some(MacvlanMode::Source),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        Ok((*self as u32).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setting::{ConnectionSettings, Setting};
+
+    #[test]
+    fn builders_round_trip() {
+        let macvlan = MacvlanSetting::new("eth0", MacvlanMode::Bridge).tap();
+        let parsed = MacvlanSetting::from_dbus(macvlan.to_dbus().unwrap()).unwrap();
+        assert_eq!(parsed, macvlan);
+        assert_eq!(parsed.parent.as_deref(), Some("eth0"));
+        assert_eq!(parsed.mode, Some(MacvlanMode::Bridge));
+        assert_eq!(parsed.tap, Some(true));
+
+        let settings = ConnectionSettings::macvlan("macvtap0", macvlan);
+        let connection = settings.connection.as_ref().unwrap();
+        assert_eq!(connection.interface_name.as_deref(), Some("macvtap0"));
+        assert_eq!(
+            connection.connection_type.as_deref(),
+            Some(MacvlanSetting::NAME)
+        );
+    }
+
+    #[test]
+    fn modes() {
+        for mode in [
+            MacvlanMode::Vepa,
+            MacvlanMode::Bridge,
+            MacvlanMode::Private,
+            MacvlanMode::Passthru,
+            MacvlanMode::Source,
+        ] {
+            assert_eq!(
+                MacvlanMode::from_value(mode.to_value().unwrap()),
+                Some(mode)
+            );
+        }
+        assert_eq!(MacvlanMode::from_value(0u32.into()), None);
+        assert_eq!(MacvlanMode::from_value(6u32.into()), None);
+    }
+}
@@ -0,0 +1,171 @@
+use bitflags::bitflags;
+use zbus::zvariant::{OwnedValue, Signature};
+
+use crate::Error;
+
+use super::{setting, Setting, SettingValue};
+
+setting! {
+    /// VLAN settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-vlan.html)
+    pub struct VlanSetting = "vlan" {
+        /// The interface name or connection UUID of the parent device.
+        parent: String = "parent",
+
+        /// The VLAN ID, between 0 and 4094.
+        id: u32 = "id",
+
+        /// Flags for the VLAN interface.
+        flags: VlanFlags = "flags",
+
+        /// How the priority of incoming frames maps to the priority of packets, as
+        /// `(from, to)` pairs.
+        ingress_priority_map: Vec<(u32, u32)> = "ingress-priority-map",
+
+        /// How the priority of outgoing packets maps to the priority of frames, as
+        /// `(from, to)` pairs.
+        egress_priority_map: Vec<(u32, u32)> = "egress-priority-map",
+
+        /// The VLAN protocol: `802.1Q` (the default) or `802.1ad`.
+        protocol: String = "protocol",
+    }
+}
+
+impl VlanSetting {
+    /// Create settings for a VLAN with the given ID on a parent device.
+    ///
+    /// The parent is either an interface name or the UUID of the parent's connection. The ID must
+    /// be between 0 and 4094, see [`VlanSetting::validate()`].
+    pub fn new(parent: impl Into<String>, id: u32) -> Self {
+        Self {
+            parent: Some(parent.into()),
+            id: Some(id),
+            ..Default::default()
+        }
+    }
+
+    /// Set the flags of the VLAN interface.
+    pub fn with_flags(mut self, flags: VlanFlags) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Map the priority `from` of incoming frames to the priority `to` of packets.
+    pub fn with_ingress_priority(mut self, from: u32, to: u32) -> Self {
+        self.ingress_priority_map
+            .get_or_insert_with(Vec::new)
+            .push((from, to));
+        self
+    }
+
+    /// Map the priority `from` of outgoing packets to the priority `to` of frames.
+    pub fn with_egress_priority(mut self, from: u32, to: u32) -> Self {
+        self.egress_priority_map
+            .get_or_insert_with(Vec::new)
+            .push((from, to));
+        self
+    }
+
+    /// Check that the VLAN ID is between 0 and 4094.
+    pub fn validate(&self) -> Result<(), Error> {
+        match self.id {
+            Some(id) if id > 4094 => Err(Error::InvalidSetting(format!(
+                "{}.id must be between 0 and 4094, got {id}",
+                Self::NAME
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+bitflags! {
+    /// Flags for a VLAN interface.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-vlan.html)
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct VlanFlags: u32 {
+        /// Output packet headers are reordered.
+        const REORDER_HEADERS = 0x1;
+
+        /// The GARP VLAN Registration Protocol is used.
+        const GVRP = 0x2;
+
+        /// The operating state of the VLAN is not bound to that of the parent.
+        const LOOSE_BINDING = 0x4;
+
+        /// The Multiple VLAN Registration Protocol is used.
+        const MVRP = 0x8;
+    }
+}
+
+impl Default for VlanFlags {
+    fn default() -> Self {
+        VlanFlags::empty()
+    }
+}
+
+impl SettingValue for VlanFlags {
+    const SIGNATURE: &'static Signature = <u32 as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        u32::try_from(value).ok().map(VlanFlags::from_bits_retain)
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        Ok(self.bits().into())
+    }
+}
+
+/// Priority maps are sent as `as`, with each mapping as `from:to`.
+impl SettingValue for Vec<(u32, u32)> {
+    const SIGNATURE: &'static Signature = <Vec<String> as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        Vec::<String>::from_value(value)?
+            .iter()
+            .map(|mapping| {
+                let (from, to) = mapping.split_once(':')?;
+                Some((from.parse().ok()?, to.parse().ok()?))
+            })
+            .collect()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        self.iter()
+            .map(|(from, to)| format!("{from}:{to}"))
+            .collect::<Vec<_>>()
+            .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_map() {
+        let map = vec!["0:3".to_owned(), "7:7".to_owned()].to_value().unwrap();
+        assert_eq!(
+            Vec::<(u32, u32)>::from_value(map),
+            Some(vec![(0, 3), (7, 7)])
+        );
+        assert_eq!(
+            vec![(2u32, 5u32)].to_value().unwrap(),
+            vec!["2:5".to_owned()].to_value().unwrap()
+        );
+
+        for invalid in ["3", "a:b", "1:2:3", ":1"] {
+            let map = vec![invalid.to_owned()].to_value().unwrap();
+            assert_eq!(Vec::<(u32, u32)>::from_value(map), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn id() {
+        assert!(VlanSetting::new("eth0", 0).validate().is_ok());
+        assert!(VlanSetting::new("eth0", 4094).validate().is_ok());
+        assert!(VlanSetting::new("eth0", 4095).validate().is_err());
+    }
+}
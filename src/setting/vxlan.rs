@@ -0,0 +1,146 @@
+use std::net::IpAddr;
+
+use crate::Error;
+
+use super::{setting, Setting};
+
+setting! {
+    /// VXLAN settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-vxlan.html)
+    pub struct VxlanSetting = "vxlan" {
+        /// The interface name or connection UUID of the device to send the traffic through.
+        parent: String = "parent",
+
+        /// The VXLAN Network Identifier (VNI).
+        id: u32 = "id",
+
+        /// The unicast destination or multicast group address to send traffic to.
+        remote: String = "remote",
+
+        /// The source address of the traffic.
+        local: String = "local",
+
+        /// The destination UDP port. Defaults to 8472.
+        destination_port: u32 = "destination-port",
+
+        /// The lowest source UDP port.
+        source_port_min: u32 = "source-port-min",
+
+        /// The highest source UDP port.
+        source_port_max: u32 = "source-port-max",
+
+        /// Whether unknown source link layer addresses and IP addresses are learned.
+        learning: bool = "learning",
+
+        /// How long learned forwarding database entries are kept, in seconds.
+        ageing: u32 = "ageing",
+
+        /// The maximum number of entries in the forwarding database. Zero means no limit.
+        limit: u32 = "limit",
+
+        /// The TTL of outgoing packets. Zero means inherited from the inner packet.
+        ttl: u32 = "ttl",
+
+        /// The type of service of outgoing packets.
+        tos: u32 = "tos",
+
+        /// Whether ARP proxy is enabled.
+        proxy: bool = "proxy",
+
+        /// Whether route short circuit is enabled.
+        rsc: bool = "rsc",
+
+        /// Whether netlink notifications are generated for link layer address misses.
+        l2_miss: bool = "l2-miss",
+
+        /// Whether netlink notifications are generated for IP address misses.
+        l3_miss: bool = "l3-miss",
+    }
+}
+
+impl VxlanSetting {
+    /// Create settings for a VXLAN with the given VNI, sending its traffic to `remote`.
+    pub fn new(id: u32, remote: IpAddr) -> Self {
+        Self {
+            id: Some(id),
+            remote: Some(remote.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Send the traffic from the given source address.
+    pub fn with_local(mut self, local: IpAddr) -> Self {
+        self.local = Some(local.to_string());
+        self
+    }
+
+    /// Send the traffic through a parent device, by interface name or connection UUID.
+    pub fn with_parent(mut self, parent: impl Into<String>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+
+    /// Set the destination UDP port.
+    pub fn with_destination_port(mut self, port: u16) -> Self {
+        self.destination_port = Some(port.into());
+        self
+    }
+
+    /// Enable or disable address learning.
+    pub fn with_learning(mut self, enabled: bool) -> Self {
+        self.learning = Some(enabled);
+        self
+    }
+
+    /// Check that the VNI is at most 16777215, as it is 24 bits.
+    pub fn validate(&self) -> Result<(), Error> {
+        match self.id {
+            Some(id) if id > MAX_VNI => Err(Error::InvalidSetting(format!(
+                "{}.id must be between 0 and {MAX_VNI}, got {id}",
+                Self::NAME
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+const MAX_VNI: u32 = (1 << 24) - 1;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setting::ConnectionSettings;
+
+    #[test]
+    fn builders_round_trip() {
+        let vxlan = VxlanSetting::new(42, "192.0.2.1".parse().unwrap())
+            .with_local("192.0.2.2".parse().unwrap())
+            .with_parent("eth0")
+            .with_destination_port(4789)
+            .with_learning(false);
+        let parsed = VxlanSetting::from_dbus(vxlan.to_dbus().unwrap()).unwrap();
+        assert_eq!(parsed, vxlan);
+        assert_eq!(parsed.id, Some(42));
+        assert_eq!(parsed.remote.as_deref(), Some("192.0.2.1"));
+        assert_eq!(parsed.local.as_deref(), Some("192.0.2.2"));
+        assert_eq!(parsed.destination_port, Some(4789));
+
+        let settings = ConnectionSettings::vxlan("vxlan42", vxlan);
+        let connection = settings.connection.as_ref().unwrap();
+        assert_eq!(connection.interface_name.as_deref(), Some("vxlan42"));
+        assert_eq!(
+            connection.connection_type.as_deref(),
+            Some(VxlanSetting::NAME)
+        );
+    }
+
+    #[test]
+    fn id() {
+        let remote = "192.0.2.1".parse().unwrap();
+        assert!(VxlanSetting::new(0, remote).validate().is_ok());
+        assert!(VxlanSetting::new(16_777_215, remote).validate().is_ok());
+        assert!(VxlanSetting::new(16_777_216, remote).validate().is_err());
+        assert!(VxlanSetting::default().validate().is_ok());
+    }
+}
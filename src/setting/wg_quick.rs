@@ -0,0 +1,598 @@
+use std::{fmt::Write, net::IpAddr};
+
+use crate::Error;
+
+use super::{
+    ConnectionSettings, Ipv4Method, Ipv4Setting, Ipv6Method, Ipv6Setting, Setting, WireGuardPeer,
+    WireGuardPrivateKey, WireGuardPublicKey, WireGuardSetting,
+};
+
+impl ConnectionSettings {
+    /// Parse a wg-quick configuration file, like `nmcli connection import type wireguard`.
+    ///
+    /// The `interface_name` is the name of the tunnel interface, which wg-quick takes from the
+    /// name of the file: `wg0` for `wg0.conf`. It is also used as the connection ID.
+    ///
+    /// The `[Interface]` section supports the `PrivateKey`, `ListenPort`, `FwMark`, `Address`,
+    /// `DNS`, `MTU` and `Table` keys, and the `[Peer]` sections the `PublicKey`, `PresharedKey`,
+    /// `AllowedIPs`, `Endpoint` and `PersistentKeepalive` keys. The `PreUp`, `PostUp`, `PreDown`,
+    /// `PostDown` and `SaveConfig` keys have no equivalent in NetworkManager and are ignored. As
+    /// with wg-quick, `DNS` entries that are not IP addresses are search domains. `Table` takes
+    /// `off`, `auto`, `main` or a table number: other table names from `/etc/iproute2/rt_tables`
+    /// are not resolved.
+    ///
+    /// The resulting settings include the secrets, and can be passed to
+    /// [`Settings::add_connection()`](crate::settings::Settings::add_connection()).
+    ///
+    /// ```rust,no_run
+    /// # use passcod_networkmanager::{setting::ConnectionSettings, Error, NetworkManager};
+    /// # async fn example(nm: NetworkManager) -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = std::fs::read_to_string("/etc/wireguard/wg0.conf")?;
+    /// let settings = ConnectionSettings::from_wg_quick("wg0", &config)?;
+    /// nm.settings().add_connection(&settings).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn from_wg_quick(interface_name: &str, config: &str) -> Result<Self, Error> {
+        if interface_name.is_empty()
+            || interface_name.len() > 15
+            || interface_name.contains(|c: char| c == '/' || c.is_whitespace())
+        {
+            return Err(Error::InvalidSetting(format!(
+                "invalid interface name: {interface_name}"
+            )));
+        }
+
+        let mut settings = Self::wireguard(interface_name, WireGuardSetting::default());
+        let wireguard = settings.wireguard.get_or_insert_with(Default::default);
+        let mut ipv4 = Ipv4Setting::new(Ipv4Method::Disabled);
+        let mut ipv6 = Ipv6Setting::new(Ipv6Method::Disabled);
+
+        let mut section = None;
+        let mut peer: Option<PeerSection> = None;
+        for (index, line) in config.lines().enumerate() {
+            let line_number = index + 1;
+            let error = |message: String| Error::Parse {
+                line: line_number,
+                message,
+            };
+
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                if let Some(peer) = peer.take() {
+                    wireguard.add_peer(peer.finish()?);
+                }
+                let name = name.trim().to_ascii_lowercase();
+                if name == "peer" {
+                    peer = Some(PeerSection::new(line_number));
+                }
+                section = Some(name);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected `key = value`, found `{line}`")))?;
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+            let number = |value: &str| {
+                value
+                    .parse::<u32>()
+                    .map_err(|_| error(format!("`{key}` must be a number, found `{value}`")))
+            };
+
+            match (section.as_deref(), key.as_str()) {
+                (Some("interface"), "privatekey") => {
+                    value.parse::<WireGuardPrivateKey>().map_err(|_| {
+                        error("`PrivateKey` must be 32 bytes encoded in base64".into())
+                    })?;
+                    wireguard.private_key = Some(value.into());
+                }
+                (Some("interface"), "listenport") => wireguard.listen_port = Some(number(value)?),
+                (Some("interface"), "fwmark") => {
+                    wireguard.fwmark = Some(match value {
+                        "off" => 0,
+                        _ => match value.strip_prefix("0x") {
+                            Some(hex) => u32::from_str_radix(hex, 16).map_err(|_| {
+                                error(format!("`FwMark` must be a number, found `{value}`"))
+                            })?,
+                            None => number(value)?,
+                        },
+                    })
+                }
+                (Some("interface"), "address") => {
+                    for address in split_list(value) {
+                        let (address, prefix) = parse_network(address)
+                            .ok_or_else(|| error(format!("invalid address `{address}`")))?;
+                        match address {
+                            IpAddr::V4(address) => {
+                                ipv4 = ipv4.with_address(address, prefix.unwrap_or(32))
+                            }
+                            IpAddr::V6(address) => {
+                                ipv6 = ipv6.with_address(address, prefix.unwrap_or(128))
+                            }
+                        }
+                    }
+                }
+                (Some("interface"), "dns") => {
+                    for server in split_list(value) {
+                        match server.parse() {
+                            Ok(IpAddr::V4(server)) => ipv4 = ipv4.with_dns(server),
+                            Ok(IpAddr::V6(server)) => ipv6 = ipv6.with_dns(server),
+                            Err(_) => ipv4
+                                .dns_search
+                                .get_or_insert_with(Vec::new)
+                                .push(server.into()),
+                        }
+                    }
+                }
+                (Some("interface"), "mtu") => wireguard.mtu = Some(number(value)?),
+                (Some("interface"), "table") => match value {
+                    "off" => wireguard.peer_routes = Some(false),
+                    "auto" => {}
+                    _ => {
+                        let table = match value {
+                            "main" => MAIN_TABLE,
+                            _ => number(value)?,
+                        };
+                        ipv4.route_table = Some(table);
+                        ipv6.route_table = Some(table);
+                    }
+                },
+                (Some("interface"), "preup" | "postup" | "predown" | "postdown" | "saveconfig") => {
+                    // scripts and saving the runtime state have no NetworkManager equivalent
+                }
+                (Some("peer"), _) => {
+                    let section = peer.as_mut().expect("peer sections start a peer");
+                    let peer = &mut section.peer;
+                    match key.as_str() {
+                        "publickey" => {
+                            section.public_key = Some(value.parse().map_err(|_| {
+                                error("`PublicKey` must be 32 bytes encoded in base64".into())
+                            })?)
+                        }
+                        "presharedkey" => peer.preshared_key = Some(value.into()),
+                        "allowedips" => peer
+                            .allowed_ips
+                            .get_or_insert_with(Vec::new)
+                            .extend(split_list(value).map(String::from)),
+                        "endpoint" => peer.endpoint = Some(value.into()),
+                        "persistentkeepalive" => {
+                            peer.persistent_keepalive = Some(match value {
+                                "off" => 0,
+                                _ => number(value)?,
+                            })
+                        }
+                        _ => return Err(error(format!("unknown key `{key}` in `[Peer]`"))),
+                    }
+                }
+                (Some("interface"), _) => {
+                    return Err(error(format!("unknown key `{key}` in `[Interface]`")))
+                }
+                (Some(section), _) => return Err(error(format!("unknown section `[{section}]`"))),
+                (None, _) => return Err(error("expected a section header first".into())),
+            }
+        }
+
+        if let Some(peer) = peer {
+            wireguard.add_peer(peer.finish()?);
+        }
+        if wireguard.private_key.is_none() {
+            return Err(Error::InvalidSetting(
+                "`PrivateKey` is required in `[Interface]`".into(),
+            ));
+        }
+        wireguard.validate()?;
+
+        if ipv4.addresses.is_some() {
+            ipv4.method = Some(Ipv4Method::Manual);
+        }
+        if ipv6.addresses.is_some() {
+            ipv6.method = Some(Ipv6Method::Manual);
+        }
+        settings.ipv4 = Some(ipv4);
+        settings.ipv6 = Some(ipv6);
+        Ok(settings)
+    }
+
+    /// Write these settings as a wg-quick configuration file.
+    ///
+    /// This is the inverse of [`ConnectionSettings::from_wg_quick()`]. The settings must include
+    /// the private key, which NetworkManager only returns as a secret: use
+    /// [`Connection::to_wg_quick()`](crate::connection::Connection::to_wg_quick()) to export a
+    /// profile, or merge the secrets in with [`WireGuardSetting::merge_secrets()`].
+    pub fn to_wg_quick(&self) -> Result<String, Error> {
+        let wireguard = self.wireguard.as_ref().ok_or_else(|| {
+            Error::InvalidSetting(format!(
+                "the {} setting is required",
+                WireGuardSetting::NAME
+            ))
+        })?;
+        let private_key = wireguard.private_key.as_ref().ok_or_else(|| {
+            Error::InvalidSetting(format!(
+                "{}.private-key is required, merge the secrets of the connection first",
+                WireGuardSetting::NAME
+            ))
+        })?;
+
+        let ipv4 = self.ipv4.as_ref();
+        let ipv6 = self.ipv6.as_ref();
+        let addresses = ipv4
+            .and_then(|ipv4| ipv4.addresses.as_ref())
+            .into_iter()
+            .flatten()
+            .map(|address| format!("{}/{}", address.address, address.prefix))
+            .chain(
+                ipv6.and_then(|ipv6| ipv6.addresses.as_ref())
+                    .into_iter()
+                    .flatten()
+                    .map(|address| format!("{}/{}", address.address, address.prefix)),
+            )
+            .collect::<Vec<_>>();
+        let dns = ipv4
+            .and_then(|ipv4| ipv4.dns.as_ref())
+            .into_iter()
+            .flatten()
+            .map(ToString::to_string)
+            .chain(
+                ipv6.and_then(|ipv6| ipv6.dns.as_ref())
+                    .into_iter()
+                    .flatten()
+                    .map(ToString::to_string),
+            )
+            .chain(
+                [ipv4.and_then(|ipv4| ipv4.dns_search.as_ref())]
+                    .into_iter()
+                    .chain([ipv6.and_then(|ipv6| ipv6.dns_search.as_ref())])
+                    .flatten()
+                    .flatten()
+                    .cloned(),
+            )
+            .collect::<Vec<_>>();
+        let table = ipv4
+            .and_then(|ipv4| ipv4.route_table)
+            .or_else(|| ipv6.and_then(|ipv6| ipv6.route_table))
+            .filter(|table| *table != 0);
+
+        let mut config = String::from("[Interface]\n");
+        writeln!(config, "PrivateKey = {private_key}").unwrap();
+        if let Some(port) = wireguard.listen_port.filter(|port| *port != 0) {
+            writeln!(config, "ListenPort = {port}").unwrap();
+        }
+        if let Some(fwmark) = wireguard.fwmark.filter(|fwmark| *fwmark != 0) {
+            writeln!(config, "FwMark = {fwmark}").unwrap();
+        }
+        if !addresses.is_empty() {
+            writeln!(config, "Address = {}", addresses.join(", ")).unwrap();
+        }
+        if !dns.is_empty() {
+            writeln!(config, "DNS = {}", dns.join(", ")).unwrap();
+        }
+        if let Some(mtu) = wireguard.mtu.filter(|mtu| *mtu != 0) {
+            writeln!(config, "MTU = {mtu}").unwrap();
+        }
+        if wireguard.peer_routes == Some(false) {
+            config.push_str("Table = off\n");
+        } else if let Some(table) = table {
+            writeln!(config, "Table = {table}").unwrap();
+        }
+
+        for peer in wireguard.peers.iter().flatten() {
+            writeln!(config, "\n[Peer]\nPublicKey = {}", peer.public_key).unwrap();
+            if let Some(preshared_key) = &peer.preshared_key {
+                writeln!(config, "PresharedKey = {preshared_key}").unwrap();
+            }
+            if let Some(allowed_ips) = peer.allowed_ips.as_ref().filter(|ips| !ips.is_empty()) {
+                writeln!(config, "AllowedIPs = {}", allowed_ips.join(", ")).unwrap();
+            }
+            if let Some(endpoint) = &peer.endpoint {
+                writeln!(config, "Endpoint = {endpoint}").unwrap();
+            }
+            if let Some(interval) = peer.persistent_keepalive.filter(|interval| *interval != 0) {
+                writeln!(config, "PersistentKeepalive = {interval}").unwrap();
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// The number of the `main` routing table.
+const MAIN_TABLE: u32 = 254;
+
+/// A `[Peer]` section being parsed, which may have its `PublicKey` anywhere.
+struct PeerSection {
+    line: usize,
+    public_key: Option<WireGuardPublicKey>,
+    peer: WireGuardPeer,
+}
+
+impl PeerSection {
+    fn new(line: usize) -> Self {
+        Self {
+            line,
+            public_key: None,
+            peer: WireGuardPeer::new(WireGuardPublicKey::from_bytes([0; 32])),
+        }
+    }
+
+    fn finish(self) -> Result<WireGuardPeer, Error> {
+        let public_key = self.public_key.ok_or_else(|| Error::Parse {
+            line: self.line,
+            message: "`PublicKey` is required in `[Peer]`".into(),
+        })?;
+        Ok(WireGuardPeer {
+            public_key,
+            ..self.peer
+        })
+    }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+fn parse_network(network: &str) -> Option<(IpAddr, Option<u8>)> {
+    let (address, prefix): (IpAddr, _) = match network.split_once('/') {
+        Some((address, prefix)) => (address.parse().ok()?, Some(prefix.parse().ok()?)),
+        None => (network.parse().ok()?, None),
+    };
+    let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+    if prefix.is_some_and(|prefix| prefix > max_prefix) {
+        return None;
+    }
+    Some((address, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "dwdtCnMYpX08FsFyUbJmRd9ML4frwJkqsXf7pR25LCo=";
+    const PEER_1: &str = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=";
+    const PEER_2: &str = "AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgI=";
+    const PRESHARED_KEY: &str = "AwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwM=";
+
+    fn parse(config: &str) -> Result<ConnectionSettings, Error> {
+        ConnectionSettings::from_wg_quick("wg0", config)
+    }
+
+    fn route_tables(settings: &ConnectionSettings) -> (Option<u32>, Option<u32>) {
+        (
+            settings.ipv4.as_ref().unwrap().route_table,
+            settings.ipv6.as_ref().unwrap().route_table,
+        )
+    }
+
+    fn parse_error_line(result: Result<ConnectionSettings, Error>) -> usize {
+        match result {
+            Err(Error::Parse { line, .. }) => line,
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let config = format!(
+            "[Interface]
+PrivateKey = {PRIVATE_KEY}
+ListenPort = 51820
+FwMark = 51820
+Address = 10.0.0.2/24, fd00::2/64
+DNS = 10.0.0.1, fd00::1, vpn.example.com
+MTU = 1420
+Table = 1234
+
+[Peer]
+PublicKey = {PEER_1}
+PresharedKey = {PRESHARED_KEY}
+AllowedIPs = 0.0.0.0/0, ::/0
+Endpoint = vpn.example.com:51820
+PersistentKeepalive = 25
+
+[Peer]
+PublicKey = {PEER_2}
+AllowedIPs = 10.0.1.0/24
+"
+        );
+
+        let settings = parse(&config).unwrap();
+        let connection = settings.connection.as_ref().unwrap();
+        assert_eq!(connection.id.as_deref(), Some("wg0"));
+        assert_eq!(connection.interface_name.as_deref(), Some("wg0"));
+
+        let wireguard = settings.wireguard.as_ref().unwrap();
+        assert_eq!(wireguard.private_key.as_deref(), Some(PRIVATE_KEY));
+        assert_eq!(wireguard.listen_port, Some(51820));
+        assert_eq!(wireguard.mtu, Some(1420));
+        assert_eq!(wireguard.peers.as_ref().unwrap().len(), 2);
+        let peer = wireguard.peer(&PEER_1.parse().unwrap()).unwrap();
+        assert_eq!(peer.preshared_key.as_deref(), Some(PRESHARED_KEY));
+        assert_eq!(peer.persistent_keepalive, Some(25));
+
+        let ipv4 = settings.ipv4.as_ref().unwrap();
+        assert_eq!(ipv4.method, Some(Ipv4Method::Manual));
+        assert_eq!(
+            ipv4.dns_search.as_deref(),
+            Some(&["vpn.example.com".into()][..])
+        );
+        assert_eq!(
+            settings.ipv6.as_ref().unwrap().method,
+            Some(Ipv6Method::Manual)
+        );
+        assert_eq!(route_tables(&settings), (Some(1234), Some(1234)));
+
+        assert_eq!(settings.to_wg_quick().unwrap(), config);
+    }
+
+    #[test]
+    fn keys_and_sections_are_case_insensitive() {
+        let settings = parse(&format!(
+            "[interface]\nprivatekey = {PRIVATE_KEY} # comment\n\n[PEER]\npublickey={PEER_1}\n"
+        ))
+        .unwrap();
+        assert!(settings
+            .wireguard
+            .unwrap()
+            .peer(&PEER_1.parse().unwrap())
+            .is_some());
+    }
+
+    #[test]
+    fn tables() {
+        let table = |table: &str| {
+            parse(&format!(
+                "[Interface]\nPrivateKey = {PRIVATE_KEY}\nTable = {table}\n"
+            ))
+        };
+
+        let settings = table("off").unwrap();
+        assert_eq!(
+            settings.wireguard.as_ref().unwrap().peer_routes,
+            Some(false)
+        );
+        assert_eq!(route_tables(&settings), (None, None));
+        assert!(settings.to_wg_quick().unwrap().contains("\nTable = off\n"));
+
+        let settings = table("auto").unwrap();
+        assert_eq!(settings.wireguard.as_ref().unwrap().peer_routes, None);
+        assert_eq!(route_tables(&settings), (None, None));
+        assert!(!settings.to_wg_quick().unwrap().contains("Table"));
+
+        assert_eq!(
+            route_tables(&table("main").unwrap()),
+            (Some(254), Some(254))
+        );
+        assert_eq!(
+            route_tables(&table("51820").unwrap()),
+            (Some(51820), Some(51820))
+        );
+        assert_eq!(parse_error_line(table("vpn")), 3);
+    }
+
+    #[test]
+    fn dns_search_domains() {
+        let settings = parse(&format!(
+            "[Interface]\nPrivateKey = {PRIVATE_KEY}\nDNS = example.com, 1.1.1.1, internal\n"
+        ))
+        .unwrap();
+        let ipv4 = settings.ipv4.as_ref().unwrap();
+        assert_eq!(ipv4.dns.as_deref(), Some(&["1.1.1.1".parse().unwrap()][..]));
+        assert_eq!(
+            ipv4.dns_search.as_deref(),
+            Some(&["example.com".into(), "internal".into()][..])
+        );
+        // there are no addresses, so the method stays disabled
+        assert_eq!(ipv4.method, Some(Ipv4Method::Disabled));
+        assert!(settings
+            .to_wg_quick()
+            .unwrap()
+            .contains("\nDNS = 1.1.1.1, example.com, internal\n"));
+    }
+
+    #[test]
+    fn public_key_after_other_peer_keys() {
+        let settings = parse(&format!(
+            "[Interface]
+PrivateKey = {PRIVATE_KEY}
+
+[Peer]
+Endpoint = vpn.example.com:51820
+AllowedIPs = 10.0.0.0/24
+PublicKey = {PEER_1}
+"
+        ))
+        .unwrap();
+        let wireguard = settings.wireguard.unwrap();
+        let peer = wireguard.peer(&PEER_1.parse().unwrap()).unwrap();
+        assert_eq!(peer.endpoint.as_deref(), Some("vpn.example.com:51820"));
+        assert_eq!(
+            peer.allowed_ips.as_deref(),
+            Some(&["10.0.0.0/24".into()][..])
+        );
+    }
+
+    #[test]
+    fn missing_public_key() {
+        let result = parse(&format!(
+            "[Interface]\nPrivateKey = {PRIVATE_KEY}\n\n[Peer]\nEndpoint = vpn.example.com:51820\n"
+        ));
+        assert_eq!(parse_error_line(result), 4);
+    }
+
+    #[test]
+    fn missing_private_key() {
+        let result = parse(&format!(
+            "[Interface]\nListenPort = 51820\n\n[Peer]\nPublicKey = {PEER_1}\n"
+        ));
+        assert!(
+            matches!(result, Err(Error::InvalidSetting(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn invalid_values() {
+        for (config, line) in [
+            ("[Interface]\nPrivateKey = nonsense\n", 2),
+            ("[Interface]\nListenPort = high\n", 2),
+            ("[Interface]\nAddress = 10.0.0.1/33\n", 2),
+            ("[Interface]\n\n[Peer]\nPublicKey = nonsense\n", 4),
+            ("PrivateKey = nonsense\n", 1),
+            ("[Interface]\nPrivateKey\n", 2),
+        ] {
+            assert_eq!(parse_error_line(parse(config)), line, "{config}");
+        }
+    }
+
+    #[test]
+    fn unknown_keys_and_sections() {
+        let unknown_key = format!("[Interface]\nPrivateKey = {PRIVATE_KEY}\nColour = blue\n");
+        assert_eq!(parse_error_line(parse(&unknown_key)), 3);
+
+        let unknown_peer_key = format!(
+            "[Interface]\nPrivateKey = {PRIVATE_KEY}\n\n[Peer]\nPublicKey = {PEER_1}\nColour = blue\n"
+        );
+        assert_eq!(parse_error_line(parse(&unknown_peer_key)), 6);
+
+        let unknown_section =
+            format!("[Interface]\nPrivateKey = {PRIVATE_KEY}\n\n[Other]\nKey = value\n");
+        assert_eq!(parse_error_line(parse(&unknown_section)), 5);
+    }
+
+    #[test]
+    fn ignored_keys() {
+        let settings = parse(&format!(
+            "[Interface]
+PrivateKey = {PRIVATE_KEY}
+PostUp = iptables -A FORWARD -i %i -j ACCEPT
+SaveConfig = true
+"
+        ))
+        .unwrap();
+        assert!(settings.wireguard.is_some());
+    }
+
+    #[test]
+    fn invalid_interface_names() {
+        for name in ["", "a-very-long-interface", "wg/0", "wg 0"] {
+            let config = format!("[Interface]\nPrivateKey = {PRIVATE_KEY}\n");
+            assert!(
+                ConnectionSettings::from_wg_quick(name, &config).is_err(),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn to_wg_quick_requires_the_private_key() {
+        let settings = ConnectionSettings::wireguard("wg0", WireGuardSetting::default());
+        assert!(settings.to_wg_quick().is_err());
+    }
+}
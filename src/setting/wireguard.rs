@@ -0,0 +1,642 @@
+use std::{collections::HashMap, fmt, net::IpAddr, str::FromStr};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use zbus::zvariant::{OwnedValue, Signature, Type};
+
+use crate::{types::SecretFlags, Error};
+
+use super::{
+    clone_properties,
+    ip::{entries, entries_value, put, take, Entry},
+    setting, Setting, SettingValue,
+};
+
+setting! {
+    /// WireGuard settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-wireguard.html)
+    ///
+    /// The peers can be changed on a running tunnel without bringing it down, see
+    /// [`WireGuardDevice::add_peer()`](crate::device::wireguard::WireGuardDevice::add_peer()).
+    pub struct WireGuardSetting = "wireguard" {
+        /// The private key of the interface, in base64.
+        private_key: String = "private-key",
+
+        /// How the [`private_key`](Self::private_key) is stored.
+        private_key_flags: SecretFlags = "private-key-flags",
+
+        /// The UDP port to listen on. If zero or None, a random port is chosen.
+        listen_port: u32 = "listen-port",
+
+        /// The firewall mark set on outgoing packets. Zero means no mark is set.
+        fwmark: u32 = "fwmark",
+
+        /// Whether routes are added for the allowed IPs of the peers.
+        peer_routes: bool = "peer-routes",
+
+        /// The MTU of the interface. Zero means the kernel default.
+        mtu: u32 = "mtu",
+
+        /// The peers of the interface.
+        peers: Vec<WireGuardPeer> = "peers",
+    }
+}
+
+impl WireGuardSetting {
+    /// Create WireGuard settings with the given private key.
+    pub fn new(private_key: &WireGuardPrivateKey) -> Self {
+        Self {
+            private_key: Some(private_key.to_base64()),
+            ..Default::default()
+        }
+    }
+
+    /// Listen on the given UDP port.
+    pub fn with_listen_port(mut self, port: u16) -> Self {
+        self.listen_port = Some(port.into());
+        self
+    }
+
+    /// Set the firewall mark on outgoing packets.
+    pub fn with_fwmark(mut self, fwmark: u32) -> Self {
+        self.fwmark = Some(fwmark);
+        self
+    }
+
+    /// Add a peer.
+    ///
+    /// See [`WireGuardSetting::add_peer()`].
+    pub fn with_peer(mut self, peer: WireGuardPeer) -> Self {
+        self.add_peer(peer);
+        self
+    }
+
+    /// Get the peer with the given public key.
+    pub fn peer(&self, public_key: &WireGuardPublicKey) -> Option<&WireGuardPeer> {
+        self.peers
+            .as_ref()?
+            .iter()
+            .find(|peer| &peer.public_key == public_key)
+    }
+
+    /// Add a peer, replacing the peer with the same public key if there is one.
+    ///
+    /// Returns the replaced peer.
+    pub fn add_peer(&mut self, peer: WireGuardPeer) -> Option<WireGuardPeer> {
+        let peers = self.peers.get_or_insert_with(Vec::new);
+        match peers
+            .iter_mut()
+            .find(|existing| existing.public_key == peer.public_key)
+        {
+            Some(existing) => Some(std::mem::replace(existing, peer)),
+            None => {
+                peers.push(peer);
+                None
+            }
+        }
+    }
+
+    /// Remove the peer with the given public key.
+    ///
+    /// Returns the removed peer, or None if there was no such peer.
+    pub fn remove_peer(&mut self, public_key: &WireGuardPublicKey) -> Option<WireGuardPeer> {
+        let peers = self.peers.as_mut()?;
+        let index = peers
+            .iter()
+            .position(|peer| &peer.public_key == public_key)?;
+        Some(peers.remove(index))
+    }
+
+    /// Merge in the secrets of the setting, as returned by
+    /// [`Connection::secrets()`](crate::connection::Connection::secrets()).
+    ///
+    /// This sets the private key and the preshared keys of the peers that are known.
+    pub fn merge_secrets(&mut self, secrets: WireGuardSetting) {
+        if secrets.private_key.is_some() {
+            self.private_key = secrets.private_key;
+        }
+
+        for secret in secrets.peers.into_iter().flatten() {
+            let peer = self
+                .peers
+                .iter_mut()
+                .flatten()
+                .find(|peer| peer.public_key == secret.public_key);
+            if let (Some(peer), Some(preshared_key)) = (peer, secret.preshared_key) {
+                peer.preshared_key = Some(preshared_key);
+            }
+        }
+    }
+
+    /// Check the keys and allowed IPs of the interface and its peers.
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(private_key) = &self.private_key {
+            private_key.parse::<WireGuardPrivateKey>().map_err(|_| {
+                Error::InvalidSetting(format!(
+                    "{}.private-key must be 32 bytes encoded in base64",
+                    Self::NAME
+                ))
+            })?;
+        }
+
+        let peers = self.peers.as_deref().unwrap_or_default();
+        for (index, peer) in peers.iter().enumerate() {
+            if peers[..index]
+                .iter()
+                .any(|other| other.public_key == peer.public_key)
+            {
+                return Err(Error::InvalidSetting(format!(
+                    "{}.peers has peer {} more than once",
+                    Self::NAME,
+                    peer.public_key
+                )));
+            }
+
+            peer.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A peer of a WireGuard interface.
+///
+/// Serialised to the `peers` property.
+#[derive(Debug, PartialEq)]
+pub struct WireGuardPeer {
+    /// The public key of the peer, which identifies it.
+    pub public_key: WireGuardPublicKey,
+
+    /// The address of the peer, as `host:port`.
+    ///
+    /// If None, the peer must connect first.
+    pub endpoint: Option<String>,
+
+    /// The networks the peer is allowed to send traffic from, and that traffic to is sent to the
+    /// peer, as addresses with an optional `/prefix`.
+    pub allowed_ips: Option<Vec<String>>,
+
+    /// The interval in seconds at which keepalive packets are sent to the peer. Zero or None
+    /// disables them.
+    pub persistent_keepalive: Option<u32>,
+
+    /// The preshared key of the peer, in base64, for an additional layer of symmetric encryption.
+    pub preshared_key: Option<String>,
+
+    /// How the [`preshared_key`](Self::preshared_key) is stored.
+    pub preshared_key_flags: Option<SecretFlags>,
+
+    /// Attributes not modelled by this crate, kept as-is.
+    pub attributes: HashMap<String, OwnedValue>,
+}
+
+impl WireGuardPeer {
+    /// Create a peer with the given public key.
+    pub fn new(public_key: WireGuardPublicKey) -> Self {
+        Self {
+            public_key,
+            endpoint: None,
+            allowed_ips: None,
+            persistent_keepalive: None,
+            preshared_key: None,
+            preshared_key_flags: None,
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Reach the peer at the given `host:port` address.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Allow the network with the given prefix length through the peer.
+    pub fn with_allowed_ip(mut self, address: IpAddr, prefix: u8) -> Self {
+        self.allowed_ips
+            .get_or_insert_with(Vec::new)
+            .push(format!("{address}/{prefix}"));
+        self
+    }
+
+    /// Send keepalive packets to the peer at the given interval, in seconds.
+    pub fn with_persistent_keepalive(mut self, interval: u16) -> Self {
+        self.persistent_keepalive = Some(interval.into());
+        self
+    }
+
+    /// Use a preshared key with the peer.
+    ///
+    /// The key is 32 random bytes in base64, as generated by `wg genpsk`.
+    pub fn with_preshared_key(mut self, preshared_key: impl Into<String>) -> Result<Self, Error> {
+        let preshared_key = preshared_key.into();
+        validate_preshared_key(&preshared_key)?;
+        self.preshared_key = Some(preshared_key);
+        Ok(self)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(preshared_key) = &self.preshared_key {
+            validate_preshared_key(preshared_key)?;
+        }
+
+        for allowed_ip in self.allowed_ips.iter().flatten() {
+            let (address, prefix) = match allowed_ip.split_once('/') {
+                Some((address, prefix)) => (address, Some(prefix)),
+                None => (allowed_ip.as_str(), None),
+            };
+            let valid = match (address.parse::<IpAddr>(), prefix.map(str::parse::<u8>)) {
+                (Ok(_), None) => true,
+                (Ok(IpAddr::V4(_)), Some(Ok(prefix))) => prefix <= 32,
+                (Ok(IpAddr::V6(_)), Some(Ok(prefix))) => prefix <= 128,
+                _ => false,
+            };
+            if !valid {
+                return Err(Error::InvalidSetting(format!(
+                    "{}.peers has an invalid allowed IP for peer {}: {allowed_ip}",
+                    WireGuardSetting::NAME,
+                    self.public_key
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_preshared_key(preshared_key: &str) -> Result<(), Error> {
+    decode_key(preshared_key).map(drop).ok_or_else(|| {
+        Error::InvalidSetting(format!(
+            "{}.peers preshared keys must be 32 bytes encoded in base64",
+            WireGuardSetting::NAME
+        ))
+    })
+}
+
+fn decode_key(key: &str) -> Option<[u8; 32]> {
+    STANDARD.decode(key).ok()?.try_into().ok()
+}
+
+fn invalid_key() -> Error {
+    Error::InvalidSetting("a WireGuard key must be 32 bytes encoded in base64".into())
+}
+
+/// The private key of a WireGuard interface.
+///
+/// Keys are Curve25519 keys, exchanged as base64 like with the `wg` tool. The key is not shown in
+/// the [`Debug`] output.
+#[derive(Clone, PartialEq, Eq)]
+pub struct WireGuardPrivateKey([u8; 32]);
+
+impl WireGuardPrivateKey {
+    /// Generate a new random private key, like `wg genkey`.
+    ///
+    /// # Panics
+    ///
+    /// If the random number generator of the operating system is unavailable.
+    pub fn generate() -> Self {
+        let mut key = [0; 32];
+        getrandom::getrandom(&mut key).expect("the system random number generator is available");
+        // clamp like `wg genkey` does, so the key is the same as it would be used
+        key[0] &= 248;
+        key[31] = (key[31] & 127) | 64;
+        Self(key)
+    }
+
+    /// Use the given raw key.
+    pub fn from_bytes(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// The raw key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// The key in base64.
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.0)
+    }
+
+    /// Derive the public key, like `wg pubkey`.
+    pub fn public_key(&self) -> WireGuardPublicKey {
+        let secret = x25519_dalek::StaticSecret::from(self.0);
+        WireGuardPublicKey(x25519_dalek::PublicKey::from(&secret).to_bytes())
+    }
+}
+
+impl FromStr for WireGuardPrivateKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_key(s).map(Self).ok_or_else(invalid_key)
+    }
+}
+
+impl fmt::Debug for WireGuardPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WireGuardPrivateKey(..)")
+    }
+}
+
+/// The public key of a WireGuard interface, which identifies it to its peers.
+///
+/// Keys are Curve25519 keys, exchanged as base64 like with the `wg` tool. The [`Display`]
+/// implementation shows the key in base64.
+///
+/// [`Display`]: fmt::Display
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WireGuardPublicKey([u8; 32]);
+
+impl WireGuardPublicKey {
+    /// Use the given raw key.
+    pub fn from_bytes(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// The raw key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// The key in base64.
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.0)
+    }
+}
+
+impl FromStr for WireGuardPublicKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_key(s).map(Self).ok_or_else(invalid_key)
+    }
+}
+
+impl fmt::Display for WireGuardPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_base64())
+    }
+}
+
+impl fmt::Debug for WireGuardPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WireGuardPublicKey")
+            .field(&self.to_base64())
+            .finish()
+    }
+}
+
+impl SettingValue for Vec<WireGuardPeer> {
+    const SIGNATURE: &'static Signature = <Vec<Entry> as Type>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        entries(value)?
+            .into_iter()
+            .map(|mut entry| {
+                Some(WireGuardPeer {
+                    public_key: take::<String>(&mut entry, "public-key")??.parse().ok()?,
+                    endpoint: take(&mut entry, "endpoint")?,
+                    allowed_ips: take(&mut entry, "allowed-ips")?,
+                    persistent_keepalive: take(&mut entry, "persistent-keepalive")?,
+                    preshared_key: take(&mut entry, "preshared-key")?,
+                    preshared_key_flags: take(&mut entry, "preshared-key-flags")?,
+                    attributes: entry,
+                })
+            })
+            .collect()
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        entries_value(
+            self.iter()
+                .map(|peer| {
+                    let mut entry = clone_properties(&peer.attributes)?;
+                    put(&mut entry, "public-key", &Some(peer.public_key.to_base64()))?;
+                    put(&mut entry, "endpoint", &peer.endpoint)?;
+                    put(&mut entry, "allowed-ips", &peer.allowed_ips)?;
+                    put(
+                        &mut entry,
+                        "persistent-keepalive",
+                        &peer.persistent_keepalive,
+                    )?;
+                    put(&mut entry, "preshared-key", &peer.preshared_key)?;
+                    put(&mut entry, "preshared-key-flags", &peer.preshared_key_flags)?;
+                    Ok(entry)
+                })
+                .collect::<Result<_, Error>>()?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setting::clone_value;
+
+    // the Diffie-Hellman vector for Alice from RFC 7748, section 6.1
+    const PRIVATE_KEY: &str = "dwdtCnMYpX08FsFyUbJmRd9ML4frwJkqsXf7pR25LCo=";
+    const PUBLIC_KEY: &str = "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo=";
+
+    fn public_key(byte: u8) -> WireGuardPublicKey {
+        WireGuardPublicKey::from_bytes([byte; 32])
+    }
+
+    fn setting_with(peers: Vec<WireGuardPeer>) -> WireGuardSetting {
+        WireGuardSetting {
+            peers: Some(peers),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn keys_round_trip_through_base64() {
+        let private_key: WireGuardPrivateKey = PRIVATE_KEY.parse().unwrap();
+        assert_eq!(private_key.to_base64(), PRIVATE_KEY);
+        assert_eq!(
+            WireGuardPrivateKey::from_bytes(*private_key.as_bytes()),
+            private_key
+        );
+
+        let public_key: WireGuardPublicKey = PUBLIC_KEY.parse().unwrap();
+        assert_eq!(public_key.to_base64(), PUBLIC_KEY);
+        assert_eq!(public_key.to_string(), PUBLIC_KEY);
+    }
+
+    #[test]
+    fn public_key_is_derived_from_private_key() {
+        let private_key: WireGuardPrivateKey = PRIVATE_KEY.parse().unwrap();
+        assert_eq!(private_key.public_key().to_base64(), PUBLIC_KEY);
+    }
+
+    #[test]
+    fn invalid_keys_are_rejected() {
+        for key in [
+            "",
+            "not base64!",
+            // 31 bytes
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA==",
+            // 33 bytes
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        ] {
+            assert!(key.parse::<WireGuardPrivateKey>().is_err(), "{key}");
+            assert!(key.parse::<WireGuardPublicKey>().is_err(), "{key}");
+        }
+    }
+
+    #[test]
+    fn generated_keys_are_clamped() {
+        for _ in 0..16 {
+            let key = WireGuardPrivateKey::generate();
+            let bytes = key.as_bytes();
+            assert_eq!(bytes[0] & 7, 0);
+            assert_eq!(bytes[31] & 128, 0);
+            assert_eq!(bytes[31] & 64, 64);
+        }
+        assert_ne!(
+            WireGuardPrivateKey::generate(),
+            WireGuardPrivateKey::generate()
+        );
+    }
+
+    #[test]
+    fn private_key_is_redacted_in_debug() {
+        let private_key: WireGuardPrivateKey = PRIVATE_KEY.parse().unwrap();
+        let debug = format!("{private_key:?}");
+        assert!(!debug.contains(PRIVATE_KEY), "{debug}");
+        assert_eq!(debug, "WireGuardPrivateKey(..)");
+    }
+
+    #[test]
+    fn valid_setting() {
+        let private_key: WireGuardPrivateKey = PRIVATE_KEY.parse().unwrap();
+        let setting = WireGuardSetting::new(&private_key)
+            .with_listen_port(51820)
+            .with_peer(
+                WireGuardPeer::new(public_key(1))
+                    .with_endpoint("vpn.example.com:51820")
+                    .with_allowed_ip("10.0.0.0".parse().unwrap(), 24)
+                    .with_allowed_ip("fd00::".parse().unwrap(), 64)
+                    .with_preshared_key(PUBLIC_KEY)
+                    .unwrap(),
+            )
+            .with_peer(WireGuardPeer::new(public_key(2)));
+        assert!(setting.validate().is_ok());
+    }
+
+    #[test]
+    fn invalid_private_key() {
+        let setting = WireGuardSetting {
+            private_key: Some("nonsense".into()),
+            ..Default::default()
+        };
+        assert!(setting.validate().is_err());
+    }
+
+    #[test]
+    fn duplicate_peers() {
+        let setting = setting_with(vec![
+            WireGuardPeer::new(public_key(1)),
+            WireGuardPeer::new(public_key(2)),
+            WireGuardPeer::new(public_key(1)),
+        ]);
+        assert!(setting.validate().is_err());
+    }
+
+    #[test]
+    fn allowed_ips() {
+        for allowed_ip in ["10.0.0.1", "0.0.0.0/0", "10.0.0.0/32", "::/0", "fd00::/128"] {
+            let mut peer = WireGuardPeer::new(public_key(1));
+            peer.allowed_ips = Some(vec![allowed_ip.into()]);
+            assert!(setting_with(vec![peer]).validate().is_ok(), "{allowed_ip}");
+        }
+
+        for allowed_ip in [
+            "10.0.0.0/33",
+            "fd00::/129",
+            "10.0.0.0/",
+            "10.0.0.0/x",
+            "nonsense",
+            "",
+        ] {
+            let mut peer = WireGuardPeer::new(public_key(1));
+            peer.allowed_ips = Some(vec![allowed_ip.into()]);
+            assert!(setting_with(vec![peer]).validate().is_err(), "{allowed_ip}");
+        }
+    }
+
+    #[test]
+    fn preshared_key_length() {
+        assert!(WireGuardPeer::new(public_key(1))
+            .with_preshared_key("AAAA")
+            .is_err());
+        assert!(WireGuardPeer::new(public_key(1))
+            .with_preshared_key("not base64!")
+            .is_err());
+
+        let mut peer = WireGuardPeer::new(public_key(1));
+        peer.preshared_key = Some("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA==".into());
+        assert!(setting_with(vec![peer]).validate().is_err());
+    }
+
+    #[test]
+    fn add_and_remove_peers() {
+        let mut setting = WireGuardSetting::default();
+        assert_eq!(setting.add_peer(WireGuardPeer::new(public_key(1))), None);
+        assert_eq!(setting.add_peer(WireGuardPeer::new(public_key(2))), None);
+
+        let replaced = setting
+            .add_peer(WireGuardPeer::new(public_key(1)).with_endpoint("vpn.example.com:51820"));
+        assert_eq!(replaced, Some(WireGuardPeer::new(public_key(1))));
+        assert_eq!(setting.peers.as_ref().unwrap().len(), 2);
+        assert_eq!(
+            setting.peer(&public_key(1)).unwrap().endpoint.as_deref(),
+            Some("vpn.example.com:51820")
+        );
+
+        assert!(setting.remove_peer(&public_key(2)).is_some());
+        assert!(setting.remove_peer(&public_key(2)).is_none());
+        assert!(setting.peer(&public_key(2)).is_none());
+    }
+
+    #[test]
+    fn merge_secrets_fills_known_peers() {
+        let mut setting = setting_with(vec![WireGuardPeer::new(public_key(1))]);
+        let mut secret = WireGuardPeer::new(public_key(1));
+        secret.preshared_key = Some(PUBLIC_KEY.into());
+        let mut unknown = WireGuardPeer::new(public_key(2));
+        unknown.preshared_key = Some(PUBLIC_KEY.into());
+        setting.merge_secrets(WireGuardSetting {
+            private_key: Some(PRIVATE_KEY.into()),
+            peers: Some(vec![secret, unknown]),
+            ..Default::default()
+        });
+
+        assert_eq!(setting.private_key.as_deref(), Some(PRIVATE_KEY));
+        assert_eq!(setting.peers.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            setting
+                .peer(&public_key(1))
+                .unwrap()
+                .preshared_key
+                .as_deref(),
+            Some(PUBLIC_KEY)
+        );
+    }
+
+    #[test]
+    fn peers_round_trip() {
+        let peers = vec![
+            WireGuardPeer::new(public_key(1))
+                .with_endpoint("vpn.example.com:51820")
+                .with_allowed_ip("10.0.0.0".parse().unwrap(), 24)
+                .with_persistent_keepalive(25),
+            WireGuardPeer::new(public_key(2)),
+        ];
+        let value = peers.to_value().unwrap();
+        assert_eq!(value.value_signature(), "aa{sv}");
+        assert_eq!(
+            Vec::<WireGuardPeer>::from_value(clone_value(&value).unwrap()),
+            Some(peers)
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use zbus::zvariant::{OwnedValue, Signature};
+
+use crate::Error;
+
+use super::{setting, string_enum, SettingValue};
+
+setting! {
+    /// Wi-Fi settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-802-11-wireless.html)
+    pub struct WirelessSetting = "802-11-wireless" {
+        /// The SSID of the network, as raw octets.
+        ssid: Vec<u8> = "ssid",
+
+        /// The operating mode of the device for this connection.
+        mode: WirelessMode = "mode",
+
+        /// The frequency band to use.
+        ///
+        /// If None, any band is used. Required with [`channel`](Self::channel).
+        band: WirelessBand = "band",
+
+        /// The channel to use, in the [`band`](Self::band).
+        ///
+        /// This is only meaningful for access point and Ad-Hoc modes; in infrastructure mode it
+        /// locks the connection to access points on that channel.
+        channel: u32 = "channel",
+
+        /// Whether the network is hidden, ie doesn't broadcast its SSID.
+        ///
+        /// If set, NetworkManager scans for the network explicitly.
+        hidden: bool = "hidden",
+
+        /// The BSSID of the access point to lock this connection to.
+        bssid: Vec<u8> = "bssid",
+
+        /// The permanent MAC address of the device this connection is locked to.
+        mac_address: Vec<u8> = "mac-address",
+
+        /// The MAC address to set on the device when activating, or one of the special values
+        /// `preserve`, `permanent`, `random` or `stable`.
+        ///
+        /// This supersedes [`mac_address_randomization`](Self::mac_address_randomization).
+        assigned_mac_address: String = "assigned-mac-address",
+
+        /// Whether to randomize the MAC address when scanning and connecting.
+        mac_address_randomization: MacAddressRandomization = "mac-address-randomization",
+
+        /// The MTU to set on the device, in bytes. Zero means the default.
+        mtu: u32 = "mtu",
+
+        /// The Wi-Fi power saving behaviour.
+        ///
+        /// 0 means the default, 1 ignores the setting, 2 disables and 3 enables power saving.
+        powersave: u32 = "powersave",
+
+        /// The BSSIDs of access points this connection was seen on, as `XX:XX:XX:XX:XX:XX`.
+        ///
+        /// This is maintained by NetworkManager.
+        seen_bssids: Vec<String> = "seen-bssids",
+    }
+}
+
+impl WirelessSetting {
+    /// Create Wi-Fi settings for the given SSID.
+    ///
+    /// Fails if the SSID is empty or longer than 32 octets.
+    pub fn new(ssid: impl Into<Vec<u8>>) -> Result<Self, Error> {
+        let ssid = ssid.into();
+        validate_ssid(&ssid)?;
+        Ok(Self {
+            ssid: Some(ssid),
+            ..Default::default()
+        })
+    }
+
+    /// Set the operating mode.
+    pub fn with_mode(mut self, mode: WirelessMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Restrict the connection to a band.
+    pub fn with_band(mut self, band: WirelessBand) -> Self {
+        self.band = Some(band);
+        self
+    }
+
+    /// Use a specific channel in a band.
+    pub fn with_channel(mut self, band: WirelessBand, channel: u32) -> Self {
+        self.band = Some(band);
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Use the channel at a frequency in MHz, as reported by
+    /// [`AccessPoint::frequency()`](crate::device::wireless::AccessPoint::frequency()).
+    ///
+    /// Fails if the frequency is not a channel in the 2.4 or 5 GHz bands, as profiles cannot
+    /// select channels in other bands.
+    pub fn with_frequency(self, frequency: u32) -> Result<Self, Error> {
+        use crate::device::wireless::{channel_from_frequency, FrequencyBand};
+
+        let band = FrequencyBand::from_frequency(frequency).and_then(|band| band.setting_band());
+        match (band, channel_from_frequency(frequency)) {
+            (Some(band), Some(channel)) => Ok(self.with_channel(band, channel)),
+            _ => Err(Error::InvalidSetting(format!(
+                "{}.channel cannot be set to {frequency} MHz",
+                <Self as super::Setting>::NAME
+            ))),
+        }
+    }
+
+    /// Mark the network as hidden.
+    pub fn hidden(mut self) -> Self {
+        self.hidden = Some(true);
+        self
+    }
+
+    /// Set the MAC address randomization behaviour.
+    pub fn with_mac_address_randomization(mut self, value: MacAddressRandomization) -> Self {
+        self.mac_address_randomization = Some(value);
+        self
+    }
+
+    /// Check that the SSID, if set, is 1 to 32 octets.
+    pub fn validate(&self) -> Result<(), Error> {
+        match &self.ssid {
+            Some(ssid) => validate_ssid(ssid),
+            None => Ok(()),
+        }
+    }
+}
+
+fn validate_ssid(ssid: &[u8]) -> Result<(), Error> {
+    if ssid.is_empty() || ssid.len() > 32 {
+        return Err(Error::InvalidSetting(format!(
+            "{}.ssid must be 1 to 32 octets, got {}",
+            <WirelessSetting as super::Setting>::NAME,
+            ssid.len()
+        )));
+    }
+    Ok(())
+}
+
+string_enum! {
+    /// The operating mode of a Wi-Fi device for a connection.
+    pub enum WirelessMode {
+        /// Connect to an access point as a client.
+        Infrastructure = "infrastructure",
+
+        /// Act as an access point.
+        Ap = "ap",
+
+        /// Join or create an Ad-Hoc network.
+        AdHoc = "adhoc",
+
+        /// Join or create an 802.11s mesh network.
+        Mesh = "mesh",
+    }
+}
+
+string_enum! {
+    /// A Wi-Fi frequency band, as used in connection settings.
+    pub enum WirelessBand {
+        /// The 5 GHz band (802.11a).
+        A = "a",
+
+        /// The 2.4 GHz band (802.11b/g).
+        Bg = "bg",
+    }
+}
+
+/// Whether to randomize the MAC address of a Wi-Fi device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MacAddressRandomization {
+    /// Use the global default.
+    Default = 0,
+
+    /// Never randomize the MAC address.
+    Never = 1,
+
+    /// Always randomize the MAC address.
+    Always = 2,
+}
+
+impl SettingValue for MacAddressRandomization {
+    const SIGNATURE: &'static Signature = <u32 as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        match u32::try_from(value).ok()? {
+            0 => Some(MacAddressRandomization::Default),
+            1 => Some(MacAddressRandomization::Never),
+            2 => Some(MacAddressRandomization::Always),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        Ok((*self as u32).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ssid_length() {
+        assert!(WirelessSetting::new("").is_err());
+        assert!(WirelessSetting::new([b'a'; 1]).is_ok());
+        assert!(WirelessSetting::new([b'a'; 32]).is_ok());
+        assert!(WirelessSetting::new([b'a'; 33]).is_err());
+
+        let setting = WirelessSetting {
+            ssid: Some(vec![0; 33]),
+            ..Default::default()
+        };
+        assert!(setting.validate().is_err());
+        assert!(WirelessSetting::default().validate().is_ok());
+    }
+}
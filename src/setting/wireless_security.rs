@@ -0,0 +1,310 @@
+use zbus::zvariant::{OwnedValue, Signature};
+
+use crate::{types::SecretFlags, Error};
+
+use super::{setting, string_enum, Setting, SettingValue};
+
+setting! {
+    /// Wi-Fi security settings.
+    ///
+    /// [Setting documentation](https://www.networkmanager.dev/docs/api/latest/settings-802-11-wireless-security.html)
+    ///
+    /// Open networks have no security settings at all. For enterprise networks
+    /// ([`KeyManagement::WpaEap`] and [`KeyManagement::WpaEapSuiteB192`]), the authentication is
+    /// configured in the `802-1x` setting.
+    pub struct WirelessSecuritySetting = "802-11-wireless-security" {
+        /// The key management scheme.
+        key_mgmt: KeyManagement = "key-mgmt",
+
+        /// The 802.11 authentication algorithm, only for WEP and LEAP: `open`, `shared` or `leap`.
+        auth_alg: String = "auth-alg",
+
+        /// The allowed WPA protocol versions: `wpa` (WPA1) and/or `rsn` (WPA2).
+        ///
+        /// If None, all versions are allowed.
+        proto: Vec<String> = "proto",
+
+        /// The allowed pairwise encryption algorithms: `tkip` and/or `ccmp`.
+        ///
+        /// If None, all algorithms are allowed.
+        pairwise: Vec<String> = "pairwise",
+
+        /// The allowed group/broadcast encryption algorithms: `wep40`, `wep104`, `tkip` and/or
+        /// `ccmp`.
+        ///
+        /// If None, all algorithms are allowed.
+        group: Vec<String> = "group",
+
+        /// Whether Protected Management Frames (802.11w) are used.
+        pmf: Pmf = "pmf",
+
+        /// The pre-shared key or passphrase for WPA-PSK, or the password for SAE.
+        psk: String = "psk",
+
+        /// How the [`psk`](Self::psk) is stored.
+        psk_flags: SecretFlags = "psk-flags",
+
+        /// WEP key at index 0.
+        wep_key0: String = "wep-key0",
+
+        /// WEP key at index 1.
+        wep_key1: String = "wep-key1",
+
+        /// WEP key at index 2.
+        wep_key2: String = "wep-key2",
+
+        /// WEP key at index 3.
+        wep_key3: String = "wep-key3",
+
+        /// How the WEP keys are stored.
+        wep_key_flags: SecretFlags = "wep-key-flags",
+
+        /// How the WEP keys are interpreted: 1 for hexadecimal or ASCII keys, 2 for passphrases.
+        wep_key_type: u32 = "wep-key-type",
+
+        /// The index of the WEP key to use, 0 to 3.
+        wep_tx_keyidx: u32 = "wep-tx-keyidx",
+
+        /// The username for LEAP authentication.
+        leap_username: String = "leap-username",
+
+        /// The password for LEAP authentication.
+        leap_password: String = "leap-password",
+
+        /// How the [`leap_password`](Self::leap_password) is stored.
+        leap_password_flags: SecretFlags = "leap-password-flags",
+    }
+}
+
+impl WirelessSecuritySetting {
+    /// Create security settings for WPA/WPA2 Personal.
+    ///
+    /// The `psk` is either a passphrase of 8 to 63 printable ASCII characters, or the raw key as
+    /// 64 hexadecimal characters.
+    pub fn wpa_psk(psk: impl Into<String>) -> Result<Self, Error> {
+        let psk = psk.into();
+        validate_psk(&psk)?;
+        Ok(Self {
+            key_mgmt: Some(KeyManagement::WpaPsk),
+            psk: Some(psk),
+            ..Default::default()
+        })
+    }
+
+    /// Create security settings for WPA3 Personal (Simultaneous Authentication of Equals).
+    ///
+    /// Unlike WPA-PSK, the SAE password has no length restriction beyond being non-empty.
+    pub fn sae(password: impl Into<String>) -> Result<Self, Error> {
+        let password = password.into();
+        if password.is_empty() {
+            return Err(Error::InvalidSetting(format!(
+                "{}.psk must not be empty for SAE",
+                Self::NAME
+            )));
+        }
+
+        Ok(Self {
+            key_mgmt: Some(KeyManagement::Sae),
+            psk: Some(password),
+            ..Default::default()
+        })
+    }
+
+    /// Create security settings for Enhanced Open (Opportunistic Wireless Encryption).
+    pub fn owe() -> Self {
+        Self {
+            key_mgmt: Some(KeyManagement::Owe),
+            ..Default::default()
+        }
+    }
+
+    /// Create security settings for WPA/WPA2/WPA3 Enterprise.
+    ///
+    /// The authentication must be configured in the `802-1x` setting.
+    pub fn wpa_eap() -> Self {
+        Self {
+            key_mgmt: Some(KeyManagement::WpaEap),
+            ..Default::default()
+        }
+    }
+
+    /// Create security settings for WPA3 Enterprise in 192-bit mode (Suite-B).
+    ///
+    /// The authentication must be configured in the `802-1x` setting, and Protected Management
+    /// Frames are required.
+    pub fn wpa_eap_suite_b_192() -> Self {
+        Self {
+            key_mgmt: Some(KeyManagement::WpaEapSuiteB192),
+            pmf: Some(Pmf::Required),
+            ..Default::default()
+        }
+    }
+
+    /// Set whether Protected Management Frames are used.
+    pub fn with_pmf(mut self, pmf: Pmf) -> Self {
+        self.pmf = Some(pmf);
+        self
+    }
+
+    /// Set how the PSK or SAE password is stored.
+    pub fn with_psk_flags(mut self, flags: SecretFlags) -> Self {
+        self.psk_flags = Some(flags);
+        self
+    }
+
+    /// Check that the settings are consistent.
+    ///
+    /// This catches mistakes NetworkManager would otherwise reject when the connection is added.
+    /// A missing PSK is not an error, as it may already be stored or be provided by a secret agent
+    /// when the connection is activated.
+    pub fn validate(&self) -> Result<(), Error> {
+        let Some(key_mgmt) = self.key_mgmt else {
+            // a key management this crate doesn't know is kept in `extra`
+            if self.extra.contains_key("key-mgmt") {
+                return Ok(());
+            }
+            return Err(Error::InvalidSetting(format!(
+                "{}.key-mgmt is required",
+                Self::NAME
+            )));
+        };
+
+        match (key_mgmt, &self.psk) {
+            (KeyManagement::WpaPsk, Some(psk)) => validate_psk(psk),
+            (KeyManagement::Sae, Some(psk)) if psk.is_empty() => Err(Error::InvalidSetting(
+                format!("{}.psk must not be empty for SAE", Self::NAME),
+            )),
+            (KeyManagement::WpaEapSuiteB192, _)
+                if self.pmf.is_some_and(|pmf| pmf != Pmf::Required) =>
+            {
+                Err(Error::InvalidSetting(format!(
+                    "{}.pmf must be required for {}",
+                    Self::NAME,
+                    key_mgmt.as_str()
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn validate_psk(psk: &str) -> Result<(), Error> {
+    let is_passphrase =
+        (8..=63).contains(&psk.len()) && psk.bytes().all(|b| b.is_ascii_graphic() || b == b' ');
+    let is_hex_key = psk.len() == 64 && psk.bytes().all(|b| b.is_ascii_hexdigit());
+    if is_passphrase || is_hex_key {
+        Ok(())
+    } else {
+        Err(Error::InvalidSetting(format!(
+            "{}.psk must be 8 to 63 ASCII characters or 64 hexadecimal digits",
+            WirelessSecuritySetting::NAME
+        )))
+    }
+}
+
+string_enum! {
+    /// The key management scheme of a Wi-Fi network.
+    pub enum KeyManagement {
+        /// Static WEP.
+        None = "none",
+
+        /// Dynamic WEP, with 802.1X.
+        Ieee8021x = "ieee8021x",
+
+        /// WPA/WPA2 Personal.
+        WpaPsk = "wpa-psk",
+
+        /// WPA3 Personal.
+        Sae = "sae",
+
+        /// Enhanced Open.
+        Owe = "owe",
+
+        /// WPA/WPA2/WPA3 Enterprise.
+        WpaEap = "wpa-eap",
+
+        /// WPA3 Enterprise in 192-bit mode.
+        WpaEapSuiteB192 = "wpa-eap-suite-b-192",
+    }
+}
+
+/// Whether Protected Management Frames (802.11w) are used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pmf {
+    /// Use the global default, which is [`Pmf::Optional`] unless configured otherwise.
+    Default = 0,
+
+    /// Disable PMF.
+    Disable = 1,
+
+    /// Enable PMF if the access point supports it.
+    Optional = 2,
+
+    /// Require PMF, and fail to connect to access points that don't support it.
+    Required = 3,
+}
+
+impl SettingValue for Pmf {
+    const SIGNATURE: &'static Signature = <i32 as SettingValue>::SIGNATURE;
+
+    fn from_value(value: OwnedValue) -> Option<Self> {
+        match i32::try_from(value).ok()? {
+            0 => Some(Pmf::Default),
+            1 => Some(Pmf::Disable),
+            2 => Some(Pmf::Optional),
+            3 => Some(Pmf::Required),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Result<OwnedValue, Error> {
+        Ok((*self as i32).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn psk_length() {
+        assert!(validate_psk("1234567").is_err());
+        assert!(validate_psk("12345678").is_ok());
+        assert!(validate_psk(&"a".repeat(63)).is_ok());
+        assert!(validate_psk(&"z".repeat(64)).is_err());
+        assert!(validate_psk("correct horse battery staple").is_ok());
+        assert!(validate_psk("pässwörd").is_err());
+        assert!(validate_psk("tab\tseparated").is_err());
+    }
+
+    #[test]
+    fn psk_hex_key() {
+        assert!(validate_psk(&"0123456789abcdef".repeat(4)).is_ok());
+        assert!(validate_psk(&"0123456789ABCDEF".repeat(4)).is_ok());
+        assert!(validate_psk(&format!("{}g", "0".repeat(63))).is_err());
+        assert!(validate_psk(&"0".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn validate() {
+        assert!(WirelessSecuritySetting::wpa_psk("short").is_err());
+        assert!(WirelessSecuritySetting::wpa_psk("long enough").is_ok());
+        assert!(WirelessSecuritySetting::default().validate().is_err());
+
+        // secrets may come from a secret agent
+        let setting = WirelessSecuritySetting {
+            key_mgmt: Some(KeyManagement::WpaPsk),
+            ..Default::default()
+        };
+        assert!(setting.validate().is_ok());
+
+        let setting = WirelessSecuritySetting {
+            psk: Some("short".into()),
+            ..setting
+        };
+        assert!(setting.validate().is_err());
+
+        let setting = WirelessSecuritySetting::wpa_eap_suite_b_192().with_pmf(Pmf::Optional);
+        assert!(setting.validate().is_err());
+    }
+}
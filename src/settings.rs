@@ -1,8 +1,8 @@
-use std::collections::HashMap;
-
-use zbus::zvariant::Value;
-
-use crate::{connection::Connection, errors::Error};
+use crate::{
+    connection::Connection,
+    errors::Error,
+    setting::{borrow_dbus, ConnectionSettings},
+};
 
 /// Connection Settings Profile Manager.
 pub struct Settings {
@@ -45,11 +45,12 @@ impl Settings {
     /// This operation does not start the network connection unless:
     /// - the device is idle and able to connect to the network described by the new connection, and
     /// - the connection is allowed to be started automatically.
-    pub async fn add_connection(
-        &self,
-        properties: HashMap<&str, HashMap<&str, Value<'_>>>,
-    ) -> Result<Connection, Error> {
-        let path = self.raw().await?.add_connection(properties).await?;
+    ///
+    /// The settings are checked with [`ConnectionSettings::validate()`] first.
+    pub async fn add_connection(&self, settings: &ConnectionSettings) -> Result<Connection, Error> {
+        settings.validate()?;
+        let raw = settings.to_dbus()?;
+        let path = self.raw().await?.add_connection(borrow_dbus(&raw)?).await?;
         Ok(Connection {
             zbus: self.zbus.clone(),
             path,
@@ -117,12 +117,66 @@ bitflags! {
     }
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive)]
+/// The overall networking state of NetworkManager.
+///
+/// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMState)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
+pub enum NmState {
+    /// Networking state is unknown.
+    ///
+    /// This indicates a daemon error that makes it unable to reasonably assess the state.
+    Unknown = 0,
+
+    /// Networking is not enabled, the system is being suspended or resumed from suspend.
+    Asleep = 10,
+
+    /// There is no active network connection.
+    Disconnected = 20,
+
+    /// Network connections are being cleaned up.
+    Disconnecting = 30,
+
+    /// A network connection is being started.
+    Connecting = 40,
+
+    /// There is only local IPv4 and/or IPv6 connectivity, but no default route to access the
+    /// Internet.
+    ConnectedLocal = 50,
+
+    /// There is only site-wide IPv4 and/or IPv6 connectivity.
+    ///
+    /// This means a default route is available, but the Internet connectivity check did not
+    /// succeed.
+    ConnectedSite = 60,
+
+    /// There is global IPv4 and/or IPv6 Internet connectivity.
+    ///
+    /// This means the Internet connectivity check succeeded.
+    ConnectedGlobal = 70,
+}
+
+/// The result of a connectivity check.
+///
+/// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMConnectivityState)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
 pub enum ConnectivityState {
+    /// Network connectivity is unknown.
+    ///
+    /// This means the connectivity checks are disabled (eg on server installations) or have not
+    /// run yet.
     Unknown = 0,
+
+    /// The host is not connected to any network.
     None = 1,
+
+    /// The Internet connection is hijacked by a captive portal gateway.
     Portal = 2,
+
+    /// The host is connected to a network, does not appear to be able to reach the full Internet,
+    /// but a captive portal has not been detected.
     Limited = 3,
+
+    /// The host is connected to a network, and appears to be able to reach the full Internet.
     Full = 4,
 }
 
@@ -201,7 +255,7 @@ bitflags! {
     }
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
 pub enum ActiveConnectionState {
     Unknown = 0,
     Activating = 1,
@@ -211,7 +265,7 @@ pub enum ActiveConnectionState {
 }
 
 /// The state of a network device.
-#[derive(Clone, Copy, Debug, FromPrimitive)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
 pub enum DeviceState {
     /// The device's state is unknown.
     Unknown = 0,
@@ -275,7 +329,7 @@ pub enum DeviceState {
 
 /// The reason a network device changed to its current state.
 // TODO: docs
-#[derive(Clone, Copy, Debug, FromPrimitive)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
 pub enum DeviceStateReason {
     None = 0,
     Unknown = 1,
@@ -446,6 +500,36 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags describing how a secret is stored and handled.
+    ///
+    /// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMSettingSecretFlags)
+    ///
+    /// The [default value](SecretFlags#default) (no flags) means the secret is stored by
+    /// NetworkManager itself, for example in the connection's keyfile.
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct SecretFlags: u32 {
+        /// The secret is stored and provided by a user session secret agent, not by
+        /// NetworkManager.
+        const AGENT_OWNED = 0x1;
+
+        /// The secret is not saved, and is requested from the user each time it is needed.
+        const NOT_SAVED = 0x2;
+
+        /// The secret is not required.
+        ///
+        /// This is used for optional secrets, like some VPN one-time passwords.
+        const NOT_REQUIRED = 0x4;
+    }
+}
+
+impl Default for SecretFlags {
+    fn default() -> Self {
+        SecretFlags::empty()
+    }
+}
+
 /// The 802.11 mode an access point can be in.
 ///
 /// This is the same as [`WirelessClientMode`] internally to NetworkManager, but is exposed as a
@@ -541,21 +625,44 @@ pub enum WirelessClientMode {
     Mesh = 4,
 }
 
-// #[derive(Clone, Copy, Debug, FromPrimitive)]
-// pub enum NMBluetoothCapabilities {
-//     NmBtCapabilityNone = 0,
-//     NmBtCapabilityDun = 1,
-//     NmBtCapabilityNap = 2,
-// }
+bitflags! {
+    /// Flags describing the network capabilities of a Bluetooth device.
+    ///
+    /// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMBluetoothCapabilities)
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BluetoothCapabilityFlags: u32 {
+        /// The device supports Bluetooth Dial-Up Networking.
+        const DUN = 0x1;
 
-// #[derive(Clone, Copy, Debug, FromPrimitive)]
-// pub enum NMDeviceModemCapabilities {
-//     NmDeviceModemCapabilityNone = 0,
-//     NmDeviceModemCapabilityPots = 1,
-//     NmDeviceModemCapabilityCdmaEvdo = 2,
-//     NmDeviceModemCapabilityGsmUmts = 4,
-//     NmDeviceModemCapabilityLte = 8,
-// }
+        /// The device supports Bluetooth Network Access Point.
+        const NAP = 0x2;
+    }
+}
+
+bitflags! {
+    /// Flags describing the generic capabilities of a modem.
+    ///
+    /// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMDeviceModemCapabilities)
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ModemCapabilityFlags: u32 {
+        /// The modem uses analog wired telephone lines.
+        const POTS = 0x1;
+
+        /// The modem uses CDMA/EVDO network technology.
+        const CDMA_EVDO = 0x2;
+
+        /// The modem uses GSM/UMTS network technology.
+        const GSM_UMTS = 0x4;
+
+        /// The modem uses LTE network technology.
+        const LTE = 0x8;
+
+        /// The modem uses 5GNR network technology.
+        const NR5G = 0x40;
+    }
+}
 
 // #[derive(Clone, Copy, Debug, FromPrimitive)]
 // pub enum NMWimaxNspNetworkType {
@@ -589,32 +696,103 @@ pub enum MeteredSetting {
     No = 2,
 }
 
-// #[derive(Clone, Copy, Debug, FromPrimitive)]
-// // pub enum NMConnectionMultiConnect {
-// //     NM_CONNECTION_MULTI_CONNECT_DEFAULT = 0,
-// //     NM_CONNECTION_MULTI_CONNECT_SINGLE = 1,
-// //     NM_CONNECTION_MULTI_CONNECT_MANUAL_MULTIPLE = 2,
-// //     NM_CONNECTION_MULTI_CONNECT_MULTIPLE = 3,
-// // }
+/// How a connection added with
+/// [`NetworkManager::add_and_activate_connection()`](crate::NetworkManager::add_and_activate_connection())
+/// is stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PersistMode {
+    /// The connection is saved to disk.
+    Disk,
 
-// #[derive(Clone, Copy, Debug, FromPrimitive)]
-// // pub enum NMActiveConnectionStateReason {
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_UNKNOWN = 0,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_NONE = 1,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_USER_DISCONNECTED = 2,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_DEVICE_DISCONNECTED = 3,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_SERVICE_STOPPED = 4,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_IP_CONFIG_INVALID = 5,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_CONNECT_TIMEOUT = 6,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_SERVICE_START_TIMEOUT = 7,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_SERVICE_START_FAILED = 8,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_NO_SECRETS = 9,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_LOGIN_FAILED = 10,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_CONNECTION_REMOVED = 11,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_DEPENDENCY_FAILED = 12,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_DEVICE_REALIZE_FAILED = 13,
-// //     NM_ACTIVE_CONNECTION_STATE_REASON_DEVICE_REMOVED = 14,
-// // }
+    /// The connection is kept in memory only, and lost when NetworkManager restarts.
+    Memory,
+
+    /// The connection is kept in memory only, and deleted when it disconnects.
+    Volatile,
+}
+
+impl PersistMode {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            PersistMode::Disk => "disk",
+            PersistMode::Memory => "memory",
+            PersistMode::Volatile => "volatile",
+        }
+    }
+}
+
+/// What the lifetime of an activation is bound to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BindActivation {
+    /// The activation is bound to the D-Bus client that requested it.
+    ///
+    /// If the client disconnects from the bus (for example because the process exits), the
+    /// connection is deactivated.
+    DbusClient,
+
+    /// The activation is not bound to anything, and outlives the requesting client.
+    None,
+}
+
+impl BindActivation {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            BindActivation::DbusClient => "dbus-client",
+            BindActivation::None => "none",
+        }
+    }
+}
+
+/// The reason an active connection changed to its current state.
+///
+/// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMActiveConnectionStateReason)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
+pub enum ActiveConnectionStateReason {
+    /// The reason for the active connection state change is unknown.
+    Unknown = 0,
+
+    /// No reason was given for the active connection state change.
+    None = 1,
+
+    /// The active connection changed state because the user disconnected it.
+    UserDisconnected = 2,
+
+    /// The active connection changed state because the device it was using was disconnected.
+    DeviceDisconnected = 3,
+
+    /// The service providing the VPN connection was stopped.
+    ServiceStopped = 4,
+
+    /// The IP config of the active connection was invalid.
+    IpConfigInvalid = 5,
+
+    /// The connection attempt to the VPN service timed out.
+    ConnectTimeout = 6,
+
+    /// A timeout occurred while starting the service providing the VPN connection.
+    ServiceStartTimeout = 7,
+
+    /// Starting the service providing the VPN connection failed.
+    ServiceStartFailed = 8,
+
+    /// Necessary secrets for the connection were not provided.
+    NoSecrets = 9,
+
+    /// Authentication to the server failed.
+    LoginFailed = 10,
+
+    /// The connection was deleted from settings.
+    ConnectionRemoved = 11,
+
+    /// The master connection failed to activate.
+    DependencyFailed = 12,
+
+    /// Could not create the software device link.
+    DeviceRealizeFailed = 13,
+
+    /// The device this connection depended on disappeared.
+    DeviceRemoved = 14,
+}
 
 // #[derive(Clone, Copy, Debug, FromPrimitive)]
 // // pub enum NMSecretAgentGetSecretsFlags {
@@ -633,38 +811,99 @@ pub enum MeteredSetting {
 // //     NM_SECRET_AGENT_CAPABILITY_VPN_HINTS = 1,
 // // }
 
-// #[derive(Clone, Copy, Debug, FromPrimitive)]
-// // pub enum NMIPTunnelMode {
-// //     NM_IP_TUNNEL_MODE_UNKNOWN = 0,
-// //     NM_IP_TUNNEL_MODE_IPIP = 1,
-// //     NM_IP_TUNNEL_MODE_GRE = 2,
-// //     NM_IP_TUNNEL_MODE_SIT = 3,
-// //     NM_IP_TUNNEL_MODE_ISATAP = 4,
-// //     NM_IP_TUNNEL_MODE_VTI = 5,
-// //     NM_IP_TUNNEL_MODE_IP6IP6 = 6,
-// //     NM_IP_TUNNEL_MODE_IPIP6 = 7,
-// //     NM_IP_TUNNEL_MODE_IP6GRE = 8,
-// //     NM_IP_TUNNEL_MODE_VTI6 = 9,
-// //     NM_IP_TUNNEL_MODE_GRETAP = 10,
-// //     NM_IP_TUNNEL_MODE_IP6GRETAP = 11,
-// // }
+/// The tunneling mode of an IP tunnel.
+///
+/// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMIPTunnelMode)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
+pub enum IpTunnelMode {
+    /// Unknown or unsupported tunnel mode.
+    Unknown = 0,
 
-// #[derive(Clone, Copy, Debug, FromPrimitive)]
-// // pub enum NMCheckpointCreateFlags {
-// //     NM_CHECKPOINT_CREATE_FLAG_NONE = 0,
-// //     NM_CHECKPOINT_CREATE_FLAG_DESTROY_ALL = 1,
-// //     NM_CHECKPOINT_CREATE_FLAG_DELETE_NEW_CONNECTIONS = 2,
-// //     NM_CHECKPOINT_CREATE_FLAG_DISCONNECT_NEW_DEVICES = 4,
-// //     NM_CHECKPOINT_CREATE_FLAG_ALLOW_OVERLAPPING = 8,
-// // }
+    /// IP in IP tunnel.
+    Ipip = 1,
 
-// #[derive(Clone, Copy, Debug, FromPrimitive)]
-// // pub enum NMRollbackResult {
-// //     NM_ROLLBACK_RESULT_OK = 0,
-// //     NM_ROLLBACK_RESULT_ERR_NO_DEVICE = 1,
-// //     NM_ROLLBACK_RESULT_ERR_DEVICE_UNMANAGED = 2,
-// //     NM_ROLLBACK_RESULT_ERR_FAILED = 3,
-// // }
+    /// GRE tunnel.
+    Gre = 2,
+
+    /// SIT tunnel.
+    Sit = 3,
+
+    /// ISATAP tunnel.
+    Isatap = 4,
+
+    /// VTI tunnel.
+    Vti = 5,
+
+    /// IPv6 in IPv6 tunnel.
+    Ip6ip6 = 6,
+
+    /// IPv4 in IPv6 tunnel.
+    Ipip6 = 7,
+
+    /// IPv6 GRE tunnel.
+    Ip6gre = 8,
+
+    /// IPv6 VTI tunnel.
+    Vti6 = 9,
+
+    /// GRETAP tunnel.
+    Gretap = 10,
+
+    /// IPv6 GRETAP tunnel.
+    Ip6gretap = 11,
+}
+
+bitflags! {
+    /// Flags for creating a checkpoint.
+    ///
+    /// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMCheckpointCreateFlags)
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct CheckpointCreateFlags: u32 {
+        /// Destroy all existing checkpoints before creating a new one.
+        const DESTROY_ALL = 0x01;
+
+        /// Upon rollback, delete any new connection added after the checkpoint.
+        const DELETE_NEW_CONNECTIONS = 0x02;
+
+        /// Upon rollback, disconnect any new device appeared after the checkpoint.
+        const DISCONNECT_NEW_DEVICES = 0x04;
+
+        /// Allow overlapping checkpoints.
+        ///
+        /// By default, creating a checkpoint fails if there are already existing checkpoints that
+        /// reference the same devices. With this flag, creation succeeds, but the older checkpoints
+        /// can no longer be rolled back once a newer one is created.
+        const ALLOW_OVERLAPPING = 0x08;
+
+        /// Upon rollback, don't preserve ports that were attached externally to a bridge device.
+        const NO_PRESERVE_EXTERNAL_PORTS = 0x10;
+    }
+}
+
+impl Default for CheckpointCreateFlags {
+    fn default() -> Self {
+        CheckpointCreateFlags::empty()
+    }
+}
+
+/// The result of a checkpoint rollback for a specific device.
+///
+/// [DBus Service documentation](https://www.networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMRollbackResult)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
+pub enum RollbackResult {
+    /// The rollback succeeded.
+    Ok = 0,
+
+    /// The device no longer exists.
+    ErrNoDevice = 1,
+
+    /// The device is now unmanaged.
+    ErrDeviceUnmanaged = 2,
+
+    /// Other errors during rollback.
+    ErrFailed = 3,
+}
 
 // #[derive(Clone, Copy, Debug, FromPrimitive)]
 // // pub enum NMSettingsAddConnection2Flags {
@@ -0,0 +1,70 @@
+use std::{future::Future, time::Duration};
+
+use async_io::Timer;
+use futures_lite::{Stream, StreamExt};
+use zbus::{
+    fdo::PropertiesProxy,
+    zvariant::{OwnedObjectPath, OwnedValue},
+};
+
+use crate::Error;
+
+/// Run a future to completion, or fail with [`Error::Timeout`] if it takes longer than `timeout`.
+pub(crate) async fn timeout<T>(
+    timeout: Duration,
+    future: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    futures_lite::future::or(future, async {
+        Timer::after(timeout).await;
+        Err(Error::Timeout)
+    })
+    .await
+}
+
+/// Map NetworkManager's null object path (`/`) to `None`.
+pub(crate) fn optional_path(path: OwnedObjectPath) -> Option<OwnedObjectPath> {
+    if path.as_str() == "/" {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+/// Subscribe to the changes of a property of the object behind a proxy.
+///
+/// The stream yields the new value each time the property changes. Changes that occurred before
+/// this is called are not reported.
+pub(crate) async fn property_changes<T>(
+    proxy: &zbus::Proxy<'_>,
+    property: &'static str,
+) -> Result<impl Stream<Item = Result<T, Error>> + Unpin, Error>
+where
+    T: TryFrom<OwnedValue, Error = zbus::zvariant::Error>,
+{
+    let interface = proxy.interface().to_owned();
+    let properties = PropertiesProxy::builder(proxy.connection())
+        .destination(proxy.destination().to_owned())?
+        .path(proxy.path().to_owned())?
+        .build()
+        .await?;
+    Ok(properties
+        .receive_properties_changed()
+        .await?
+        .filter_map(move |signal| {
+            let args = match signal.args() {
+                Ok(args) => args,
+                Err(error) => return Some(Err(error.into())),
+            };
+            if *args.interface_name() != interface {
+                return None;
+            }
+
+            let value = args.changed_properties().get(property)?;
+            Some(
+                value
+                    .try_to_owned()
+                    .and_then(T::try_from)
+                    .map_err(|error| Error::ZBus(error.into())),
+            )
+        }))
+}